@@ -0,0 +1,43 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lock2::arena::merge::merge_walls;
+
+const SIZE: usize = 256;
+
+/// A walled-in map with noisy interior walls, filled from a fixed xorshift so every run is equal.
+fn noisy_map(fill: u32) -> Vec<Vec<bool>> {
+    let mut state: u32 = 0x9e37_79b9;
+    (0..SIZE)
+        .map(|y| {
+            (0..SIZE)
+                .map(|x| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    y == 0 || x == 0 || y == SIZE - 1 || x == SIZE - 1 || state % 100 < fill
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn bench_merge(c: &mut Criterion) {
+    let maps = [
+        ("solid", vec![vec![true; SIZE]; SIZE]),
+        ("noise_45", noisy_map(45)),
+        (
+            "checkerboard",
+            (0..SIZE)
+                .map(|y| (0..SIZE).map(|x| (x + y) % 2 == 0).collect())
+                .collect(),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("merge_walls_256x256");
+    for (label, map) in maps.iter() {
+        group.bench_function(*label, |b| b.iter(|| merge_walls(black_box(map))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_merge);
+criterion_main!(benches);
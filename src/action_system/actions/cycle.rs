@@ -0,0 +1,103 @@
+use bevy::{
+    app::App,
+    hierarchy::Children,
+    prelude::{Commands, Component, Entity, Query, Transform, Trigger},
+    reflect::Reflect,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+
+use crate::{action_system::actuator::Actuate, util::add_observer_to_component};
+
+/// Fires the actions on just one of its `CycleStep` children each time, going round them in
+/// order.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct ActionCycle {
+    pub next: usize,
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct CycleStep(pub usize);
+
+impl ActionCycle {
+    pub fn setup(app: &mut App) {
+        app.register_type::<ActionCycle>()
+            .register_type::<CycleStep>();
+        app.add_observer(add_observer_to_component::<ActionCycle, _, _, _, _>(
+            do_cycle_action,
+        ));
+    }
+}
+
+/// Takes turns between each of `payloads`.
+pub fn cycle(payloads: Vec<ComponentTree>) -> ComponentTree {
+    payloads.into_iter().enumerate().fold(
+        ActionCycle { next: 0 }.store(),
+        |tree, (index, actions)| {
+            tree << (actions + (CycleStep(index), Transform::default()).store())
+        },
+    )
+}
+
+pub fn do_cycle_action(
+    trigger: Trigger<Actuate>,
+    mut cycles: Query<(&mut ActionCycle, &Children)>,
+    steps: Query<&CycleStep>,
+    mut commands: Commands,
+) {
+    let Ok((mut cycle, children)) = cycles.get_mut(trigger.entity()) else {
+        return;
+    };
+    let mut ordered: Vec<(Entity, usize)> = children
+        .iter()
+        .filter_map(|w| steps.get(*w).ok().map(|step| (*w, step.0)))
+        .collect();
+    if ordered.is_empty() {
+        return;
+    }
+    ordered.sort_by_key(|w| w.1);
+    let (step, _) = ordered[cycle.next % ordered.len()];
+    cycle.next = (cycle.next + 1) % ordered.len();
+    commands.trigger_targets(Actuate, step);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        hierarchy::BuildChildren,
+        prelude::{ResMut, Resource},
+    };
+
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Fired(Vec<usize>);
+
+    #[test]
+    fn goes_round_the_steps_in_order() {
+        let mut app = App::new();
+        ActionCycle::setup(&mut app);
+        app.init_resource::<Fired>();
+        app.add_observer(
+            |trigger: Trigger<Actuate>, steps: Query<&CycleStep>, mut fired: ResMut<Fired>| {
+                if let Ok(CycleStep(index)) = steps.get(trigger.entity()) {
+                    fired.0.push(*index);
+                }
+            },
+        );
+        let cycle = app
+            .world_mut()
+            .spawn(ActionCycle { next: 0 })
+            .with_children(|steps| {
+                for index in [2, 0, 1] {
+                    steps.spawn(CycleStep(index));
+                }
+            })
+            .id();
+
+        for _ in 0..7 {
+            app.world_mut().trigger_targets(Actuate, cycle);
+            app.world_mut().flush();
+        }
+        assert_eq!(app.world().resource::<Fired>().0, [0, 1, 2, 0, 1, 2, 0]);
+    }
+}
@@ -0,0 +1,137 @@
+use avian2d::prelude::{CollisionLayers, ExternalImpulse, LayerMask};
+use bevy::{
+    app::{App, Update},
+    ecs::schedule::IntoSystemConfigs,
+    hierarchy::{HierarchyQueryExt, Parent},
+    math::Vec2,
+    prelude::{Commands, Component, Entity, GlobalTransform, Query, Res, Trigger},
+    reflect::Reflect,
+    time::{Time, Timer, TimerMode},
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    action_system::{actuator::Actuate, ActuatorLogicPhases},
+    transform2d::To2D,
+    twin_stick::actors::{Actor, Invulnerable},
+    util::add_observer_to_component,
+};
+
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DashDirection {
+    /// The way the owner is trying to move, or where the action faces if it is standing still.
+    Movement,
+    /// Where the action faces, e.g. at the cursor.
+    Facing,
+}
+
+/// Throws the owning actor. For `invulnerable` seconds afterwards it can't be hurt and passes
+/// through anything on the `pass_through` layers.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct DashAction {
+    pub impulse: f32,
+    pub direction: DashDirection,
+    pub invulnerable: f32,
+    pub pass_through: LayerMask,
+}
+
+impl DashAction {
+    pub fn new(impulse: f32) -> Self {
+        Self {
+            impulse,
+            direction: DashDirection::Movement,
+            invulnerable: 0.,
+            pass_through: LayerMask::NONE,
+        }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.register_type::<DashAction>();
+        app.add_systems(Update, end_dashes.in_set(ActuatorLogicPhases::PostActuate));
+        app.add_observer(add_observer_to_component::<DashAction, _, _, _, _>(
+            do_dash_action,
+        ));
+    }
+}
+
+pub fn dash(impulse: f32) -> ComponentTree {
+    DashAction::new(impulse).store()
+}
+
+/// An actor in the middle of a dash, and the collision layers to give back once it is over.
+#[derive(Component, Clone, Debug)]
+pub struct Dashing {
+    pub timer: Timer,
+    pub layers: Option<CollisionLayers>,
+}
+
+pub fn do_dash_action(
+    trigger: Trigger<Actuate>,
+    dashes: Query<(&DashAction, &GlobalTransform)>,
+    mut actors: Query<(
+        &Actor,
+        &mut ExternalImpulse,
+        Option<&mut CollisionLayers>,
+        Option<&mut Dashing>,
+    )>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    let e = trigger.entity();
+    let Ok((dash, transform)) = dashes.get(e) else {
+        return;
+    };
+    let Some(owner) = std::iter::once(e)
+        .chain(parents.iter_ancestors(e))
+        .find(|w| actors.contains(*w))
+    else {
+        return;
+    };
+    let (actor, mut impulse, layers, dashing) = actors.get_mut(owner).unwrap();
+
+    let facing = Vec2::from_angle(transform.rotation().to_2d() + FRAC_PI_2);
+    let direction = match dash.direction {
+        DashDirection::Movement => actor.desired_direction.try_normalize().unwrap_or(facing),
+        DashDirection::Facing => facing,
+    };
+    impulse.apply_impulse(direction * dash.impulse);
+
+    if dash.invulnerable <= 0. {
+        return;
+    }
+    let timer = Timer::from_seconds(dash.invulnerable, TimerMode::Once);
+    // Dashing again mid-dash only extends it, the layers to restore are still the original ones.
+    if let Some(mut dashing) = dashing {
+        dashing.timer = timer;
+        return;
+    }
+    let restore = layers.map(|mut layers| {
+        let original = *layers;
+        layers.filters.remove(dash.pass_through);
+        original
+    });
+    commands.entity(owner).insert((
+        Dashing {
+            timer,
+            layers: restore,
+        },
+        Invulnerable,
+    ));
+}
+
+pub fn end_dashes(
+    mut dashers: Query<(Entity, &mut Dashing, Option<&mut CollisionLayers>)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut dashing, layers) in dashers.iter_mut() {
+        if !dashing.timer.tick(time.delta()).finished() {
+            continue;
+        }
+        if let (Some(mut layers), Some(original)) = (layers, dashing.layers) {
+            *layers = original;
+        }
+        commands.entity(entity).remove::<(Dashing, Invulnerable)>();
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::{
+    app::App,
+    hierarchy::{HierarchyQueryExt, Parent},
+    math::Vec3Swizzles,
+    prelude::{Component, Entity, EventWriter, GlobalTransform, Query, Transform, Trigger, With},
+    reflect::Reflect,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::{
+    statmod::{ModType, StatValueChange},
+    Resource, ResourceChangeEvent,
+};
+
+use crate::{
+    action_system::actuator::Actuate,
+    game::stats::{Health, ResourceMax},
+    twin_stick::actors::{Actor, Faction},
+    util::add_observer_to_component,
+};
+
+/// Restores `amount` health to every ally of the owner within `radius`, but not the owner itself.
+/// Allies are never healed past their `ResourceMax`.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct HealAction {
+    pub radius: f32,
+    pub amount: f32,
+}
+
+impl HealAction {
+    pub fn setup(app: &mut App) {
+        app.register_type::<HealAction>();
+        app.add_observer(add_observer_to_component::<HealAction, _, _, _, _>(
+            do_heal_action,
+        ));
+    }
+}
+
+pub fn heal(radius: f32, amount: f32) -> ComponentTree {
+    HealAction { radius, amount }.store()
+}
+
+pub fn do_heal_action(
+    trigger: Trigger<Actuate>,
+    healers: Query<(&HealAction, &GlobalTransform)>,
+    allies: Query<
+        (
+            Entity,
+            &Transform,
+            &Faction,
+            &Resource<Health>,
+            Option<&ResourceMax<Health>>,
+        ),
+        With<Actor>,
+    >,
+    parents: Query<&Parent>,
+    mut health_changes: EventWriter<ResourceChangeEvent<Health>>,
+) {
+    let e = trigger.entity();
+    let Ok((heal, transform)) = healers.get(e) else {
+        return;
+    };
+    let Some((owner, _, faction, _, _)) =
+        parents.iter_ancestors(e).find_map(|w| allies.get(w).ok())
+    else {
+        return;
+    };
+    let center = transform.translation().xy();
+    for (ally, _, _, health, max) in allies
+        .iter()
+        .filter(|(ally, _, ally_faction, _, _)| *ally != owner && *ally_faction == faction)
+        .filter(|(_, w, _, _, _)| w.translation.xy().distance(center) <= heal.radius)
+    {
+        let room = max.map_or(heal.amount, |w| w.value - health.current_value());
+        let amount = heal.amount.min(room);
+        if amount > 0. {
+            health_changes.send(ResourceChangeEvent {
+                change: StatValueChange::new(amount, ModType::Offset),
+                target: ally,
+            });
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use avian2d::prelude::{CollisionLayers, LayerMask, SpatialQuery, SpatialQueryFilter};
+use bevy::{
+    app::{App, Update},
+    color::palettes::css::{ORANGE_RED, WHITE},
+    ecs::{schedule::IntoSystemConfigs, system::SystemParam},
+    hierarchy::{HierarchyQueryExt, Parent},
+    math::{Dir2, Vec2, Vec3Swizzles},
+    prelude::{
+        Commands, Component, Entity, EventWriter, Gizmos, GlobalTransform, Query, Res, Trigger,
+        With,
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::Resource;
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    action_system::{
+        actuator::{Actuate, ActuatorCondition},
+        ActuatorLogicPhases,
+    },
+    game::stats::Health,
+    transform2d::To2D,
+    twin_stick::{
+        actors::{Actor, Faction},
+        events::AttackEvent,
+        physics::GamePhysicsLayer as GPL,
+        projectile::{projectile_layers, Lifespan},
+        weapons::faction_of,
+    },
+    util::add_observer_to_component,
+};
+
+/// Hits whatever is in front of the weapon out to `range` in an instant, passing through `pierce`
+/// targets before stopping. Walls always stop it.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct HitscanAction {
+    pub range: f32,
+    pub pierce: u32,
+}
+
+/// A hitscan that stays on while the actuator's condition holds, dealing the weapon's `Damage` and
+/// `Knockback` every second instead of on each hit.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct BeamAction {
+    pub range: f32,
+    pub pierce: u32,
+}
+
+/// The line a hitscan shot left behind, drawn until its `Lifespan` runs out.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct Tracer {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl HitscanAction {
+    pub fn setup(app: &mut App) {
+        app.register_type::<HitscanAction>()
+            .register_type::<BeamAction>()
+            .register_type::<Tracer>();
+        app.add_systems(Update, fire_beams.in_set(ActuatorLogicPhases::PostActuate));
+        app.add_systems(Update, draw_tracers);
+        app.add_observer(add_observer_to_component::<HitscanAction, _, _, _, _>(
+            do_hitscan_action,
+        ));
+    }
+}
+
+pub fn hitscan(range: f32, pierce: u32) -> ComponentTree {
+    HitscanAction { range, pierce }.store()
+}
+
+pub fn beam(range: f32, pierce: u32) -> ComponentTree {
+    BeamAction { range, pierce }.store()
+}
+
+/// Everything `trace` needs to look up.
+#[derive(SystemParam)]
+pub struct TraceQueries<'w, 's> {
+    spatial_query: SpatialQuery<'w, 's>,
+    actors: Query<'w, 's, (), With<Actor>>,
+    factions: Query<'w, 's, &'static Faction>,
+    parents: Query<'w, 's, &'static Parent>,
+    layers: Query<'w, 's, &'static CollisionLayers>,
+    targets: Query<'w, 's, (), With<Resource<Health>>>,
+}
+
+/// What a ray from a weapon went through, and where it stopped.
+struct Trace {
+    owner: Entity,
+    hits: Vec<(Entity, Vec2)>,
+    start: Vec2,
+    end: Vec2,
+    direction: Vec2,
+}
+
+fn trace(
+    weapon: Entity,
+    transform: &GlobalTransform,
+    range: f32,
+    pierce: u32,
+    queries: &TraceQueries,
+) -> Trace {
+    let owner = std::iter::once(weapon)
+        .chain(queries.parents.iter_ancestors(weapon))
+        .find(|w| queries.actors.contains(*w))
+        .unwrap_or(weapon);
+    // The ray stops on whatever the owner's bullets would.
+    let mask = faction_of(owner, &queries.factions, &queries.parents).map_or(
+        LayerMask::from([GPL::Player, GPL::Enemy, GPL::MapSolid, GPL::MapDynamic]),
+        |w| projectile_layers(w).filters,
+    );
+    let filter = SpatialQueryFilter::from_mask(mask).with_excluded_entities([owner]);
+
+    let (_, rotation, translation) = transform.to_scale_rotation_translation();
+    let start = translation.xy();
+    let direction = Vec2::from_angle(rotation.to_2d() + FRAC_PI_2);
+    // Ray hits come back in no particular order, so every one has to be collected before the
+    // nearest wall can be found.
+    let mut hits = Vec::new();
+    queries.spatial_query.ray_hits_callback(
+        start,
+        Dir2::new(direction).unwrap_or(Dir2::Y),
+        range,
+        true,
+        &filter,
+        |hit| {
+            hits.push(hit);
+            true
+        },
+    );
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    let mut trace = Trace {
+        owner,
+        hits: Vec::new(),
+        start,
+        end: start + direction * range,
+        direction,
+    };
+    for hit in hits {
+        let at = start + direction * hit.distance;
+        if queries.targets.contains(hit.entity) {
+            trace.hits.push((hit.entity, at));
+        }
+        let solid = queries
+            .layers
+            .get(hit.entity)
+            .is_ok_and(|w| w.memberships.has_all(GPL::MapSolid));
+        if solid || trace.hits.len() > pierce as usize {
+            trace.end = at;
+            break;
+        }
+    }
+    trace
+}
+
+pub fn do_hitscan_action(
+    trigger: Trigger<Actuate>,
+    hitscans: Query<(&HitscanAction, &GlobalTransform)>,
+    queries: TraceQueries,
+    mut attack_events: EventWriter<AttackEvent>,
+    mut commands: Commands,
+) {
+    let e = trigger.entity();
+    let Ok((hitscan, transform)) = hitscans.get(e) else {
+        return;
+    };
+    let trace = trace(e, transform, hitscan.range, hitscan.pierce, &queries);
+    for (defender, location) in trace.hits.iter() {
+        attack_events.send(AttackEvent {
+            attacker: trace.owner,
+            weapon: e,
+            defender: *defender,
+            location: *location,
+            direction: trace.direction,
+            scale: 1.,
+        });
+    }
+    commands.spawn((
+        Tracer {
+            start: trace.start,
+            end: trace.end,
+        },
+        Lifespan::new(0.1),
+    ));
+}
+
+pub fn fire_beams(
+    beams: Query<(Entity, &BeamAction, &GlobalTransform), With<ActuatorCondition>>,
+    queries: TraceQueries,
+    time: Res<Time>,
+    mut attack_events: EventWriter<AttackEvent>,
+    mut gizmos: Gizmos,
+) {
+    for (e, beam, transform) in beams.iter() {
+        let trace = trace(e, transform, beam.range, beam.pierce, &queries);
+        for (defender, location) in trace.hits.iter() {
+            attack_events.send(AttackEvent {
+                attacker: trace.owner,
+                weapon: e,
+                defender: *defender,
+                location: *location,
+                direction: trace.direction,
+                scale: time.delta().as_secs_f32(),
+            });
+        }
+        gizmos.line_2d(trace.start, trace.end, ORANGE_RED);
+    }
+}
+
+pub fn draw_tracers(tracers: Query<&Tracer>, mut gizmos: Gizmos) {
+    for tracer in tracers.iter() {
+        gizmos.line_2d(tracer.start, tracer.end, WHITE);
+    }
+}
@@ -0,0 +1,318 @@
+use avian2d::prelude::{
+    Collider, ColliderDensity, CollisionLayers, LayerMask, LinearVelocity, PhysicsLayer, Sensor,
+    SpatialQuery, SpatialQueryFilter,
+};
+use bevy::{
+    app::{App, Update},
+    ecs::schedule::IntoSystemConfigs,
+    hierarchy::{BuildChildren, HierarchyQueryExt, Parent},
+    math::{Vec2, Vec3Swizzles},
+    prelude::{
+        Commands, Component, Entity, EventWriter, GlobalTransform, OnRemove, Query, Res, Transform,
+        Trigger, With, Without,
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::{
+    statmod::{ModType, StatModifier, StatValueChange},
+    DeleteStatMod, Resource, Stat,
+};
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    action_system::{actuator::Actuate, ActuatorLogicPhases},
+    game::stats::{Health, MoveSpeed},
+    transform2d::To2D,
+    twin_stick::{
+        actors::{Actor, Faction},
+        events::AttackEvent,
+        physics::GamePhysicsLayer as GPL,
+        projectile::{Lifespan, Projectile},
+        weapons::faction_of,
+    },
+    util::add_observer_to_component,
+};
+
+use super::spawn::SpawnedBy;
+
+/// The area a melee swing covers, reaching out along the weapon's facing.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum HitboxShape {
+    Circle(f32),
+    Box(Vec2),
+    /// A slice of a circle `angle` radians wide.
+    Arc {
+        radius: f32,
+        angle: f32,
+    },
+}
+
+impl HitboxShape {
+    pub fn collider(&self) -> Collider {
+        match *self {
+            HitboxShape::Circle(radius) => Collider::circle(radius),
+            HitboxShape::Box(size) => Collider::rectangle(size.x, size.y),
+            HitboxShape::Arc { radius, angle } => {
+                let points = std::iter::once(Vec2::ZERO)
+                    .chain((0..=8).map(|i| {
+                        Vec2::from_angle(FRAC_PI_2 - angle / 2. + angle * i as f32 / 8.) * radius
+                    }))
+                    .collect();
+                Collider::convex_hull(points).unwrap_or(Collider::circle(radius))
+            }
+        }
+    }
+}
+
+/// Swings at whatever is in front of the weapon: after `windup` seconds, during which the owner
+/// moves at `self_slow` of its speed, a hitbox `reach` ahead of the weapon hits everything in it
+/// once for `duration` seconds. A `reflect`ing swing also knocks bullets back the way they came.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct MeleeAction {
+    pub shape: HitboxShape,
+    pub reach: f32,
+    pub windup: f32,
+    pub duration: f32,
+    pub self_slow: f32,
+    pub reflect: bool,
+}
+
+impl MeleeAction {
+    pub fn new(shape: HitboxShape, reach: f32, windup: f32, duration: f32) -> Self {
+        Self {
+            shape,
+            reach,
+            windup,
+            duration,
+            self_slow: 1.,
+            reflect: false,
+        }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.register_type::<MeleeAction>()
+            .register_type::<MeleeHitbox>();
+        app.add_systems(
+            Update,
+            (swing_melee, melee_hits).in_set(ActuatorLogicPhases::PostActuate),
+        );
+        app.add_observer(add_observer_to_component::<MeleeAction, _, _, _, _>(
+            do_melee_action,
+        ));
+        app.add_observer(end_swing_slow);
+    }
+}
+
+pub fn melee(action: MeleeAction) -> ComponentTree {
+    action.store()
+}
+
+/// A swing in progress, from the start of the windup until the hitbox is gone.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MeleeSwing {
+    pub owner: Option<Entity>,
+    pub elapsed: f32,
+    pub slow: Option<Entity>,
+    pub struck: bool,
+}
+
+#[derive(Component, Reflect, Clone, Debug)]
+pub struct MeleeHitbox {
+    pub attacker: Entity,
+    pub weapon: Entity,
+    pub reflect: bool,
+    pub hit: Vec<Entity>,
+}
+
+pub fn do_melee_action(
+    trigger: Trigger<Actuate>,
+    swings: Query<&MeleeAction, Without<MeleeSwing>>,
+    mut actors: Query<Option<&mut Stat<MoveSpeed>>, With<Actor>>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    let e = trigger.entity();
+    // A swing can't be started again until the last one is over.
+    let Ok(action) = swings.get(e) else {
+        return;
+    };
+    let owner = std::iter::once(e)
+        .chain(parents.iter_ancestors(e))
+        .find(|w| actors.contains(*w));
+    let mut slow = None;
+    if let Some(Ok(Some(mut speed))) = owner.map(|w| actors.get_mut(w)) {
+        if action.self_slow != 1. && action.windup > 0. {
+            let stat_mod = commands
+                .spawn((
+                    StatValueChange::<MoveSpeed>::new(action.self_slow, ModType::Multiplier),
+                    StatModifier,
+                ))
+                .id();
+            speed.add_mod(stat_mod);
+            slow = Some(stat_mod);
+        }
+    }
+    commands.entity(e).insert(MeleeSwing {
+        owner,
+        elapsed: 0.,
+        slow,
+        struck: false,
+    });
+}
+
+pub fn swing_melee(
+    mut swings: Query<(Entity, &MeleeAction, &mut MeleeSwing)>,
+    time: Res<Time>,
+    mut deletions: EventWriter<DeleteStatMod>,
+    mut commands: Commands,
+) {
+    for (entity, action, mut swing) in swings.iter_mut() {
+        swing.elapsed += time.delta().as_secs_f32();
+        if !swing.struck && swing.elapsed >= action.windup {
+            swing.struck = true;
+            if let Some(slow) = swing.slow.take() {
+                deletions.send(DeleteStatMod(slow));
+            }
+            commands
+                .spawn((
+                    MeleeHitbox {
+                        attacker: swing.owner.unwrap_or(entity),
+                        weapon: entity,
+                        reflect: action.reflect,
+                        hit: Vec::new(),
+                    },
+                    action.shape.collider(),
+                    Sensor,
+                    // Hits are found with a shape query, so the hitbox itself touches nothing.
+                    CollisionLayers::new(GPL::Trigger, LayerMask::NONE),
+                    ColliderDensity(0.),
+                    Transform::from_xyz(0., action.reach, 0.),
+                    Lifespan::new(action.duration),
+                ))
+                .set_parent(entity);
+        }
+        if swing.elapsed >= action.windup + action.duration {
+            commands.entity(entity).remove::<MeleeSwing>();
+        }
+    }
+}
+
+/// A swing that goes away before its windup ends, because the weapon was unequipped, dropped or
+/// despawned, still has to give the owner their speed back.
+pub fn end_swing_slow(
+    trigger: Trigger<OnRemove, MeleeSwing>,
+    swings: Query<&MeleeSwing>,
+    mut deletions: EventWriter<DeleteStatMod>,
+) {
+    if let Some(slow) = swings.get(trigger.entity()).ok().and_then(|w| w.slow) {
+        deletions.send(DeleteStatMod(slow));
+    }
+}
+
+/// Swap which side a bullet belongs to, for bullets nobody's side owns.
+fn reflected(layers: &CollisionLayers) -> CollisionLayers {
+    let mut memberships = layers.memberships;
+    let mut filters = layers.filters;
+    for (a, b) in [(GPL::Bullet, GPL::EnemyBullet), (GPL::Enemy, GPL::Player)] {
+        for mask in [&mut memberships, &mut filters] {
+            let (has_a, has_b) = (mask.has_all(a.to_bits()), mask.has_all(b.to_bits()));
+            mask.remove(a.to_bits() | b.to_bits());
+            if has_a {
+                mask.add(b.to_bits());
+            }
+            if has_b {
+                mask.add(a.to_bits());
+            }
+        }
+    }
+    CollisionLayers::new(memberships, filters)
+}
+
+pub fn melee_hits(
+    mut hitboxes: Query<(&mut MeleeHitbox, &Collider, &GlobalTransform)>,
+    spatial_query: SpatialQuery,
+    targets: Query<&GlobalTransform, With<Resource<Health>>>,
+    mut bullets: Query<(&mut LinearVelocity, &mut CollisionLayers), With<Projectile>>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
+    mut attack_events: EventWriter<AttackEvent>,
+    mut commands: Commands,
+) {
+    for (mut hitbox, collider, transform) in hitboxes.iter_mut() {
+        let (_, rotation, translation) = transform.to_scale_rotation_translation();
+        let origin = translation.xy();
+        let forward = Vec2::from_angle(rotation.to_2d() + FRAC_PI_2);
+        let filter = SpatialQueryFilter::from_mask([
+            GPL::Player,
+            GPL::Enemy,
+            GPL::MapDynamic,
+            GPL::Bullet,
+            GPL::EnemyBullet,
+        ])
+        .with_excluded_entities([hitbox.attacker]);
+        for entity in spatial_query.shape_intersections(collider, origin, rotation.to_2d(), &filter)
+        {
+            if hitbox.hit.contains(&entity) {
+                continue;
+            }
+            if let Ok(target) = targets.get(entity) {
+                hitbox.hit.push(entity);
+                attack_events.send(AttackEvent {
+                    attacker: hitbox.attacker,
+                    weapon: hitbox.weapon,
+                    defender: entity,
+                    location: target.translation().xy(),
+                    direction: (target.translation().xy() - origin)
+                        .try_normalize()
+                        .unwrap_or(forward),
+                    scale: 1.,
+                });
+            } else if let (true, Ok((mut velocity, mut layers))) =
+                (hitbox.reflect, bullets.get_mut(entity))
+            {
+                hitbox.hit.push(entity);
+                velocity.0 = -velocity.0;
+                // Taking the swinger's side gives the bullet their collision layers.
+                match faction_of(hitbox.attacker, &factions, &parents) {
+                    Some(faction) => {
+                        commands.entity(entity).insert(faction);
+                    }
+                    None => *layers = reflected(&layers),
+                }
+                commands.entity(entity).insert(SpawnedBy(hitbox.weapon));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Events;
+
+    use super::*;
+
+    fn swing(slow: Option<Entity>) -> MeleeSwing {
+        MeleeSwing {
+            owner: None,
+            elapsed: 0.,
+            slow,
+            struck: false,
+        }
+    }
+
+    #[test]
+    fn despawning_mid_windup_removes_the_slow() {
+        let mut app = App::new();
+        app.add_event::<DeleteStatMod>();
+        app.add_observer(end_swing_slow);
+        let slow = app.world_mut().spawn(StatModifier).id();
+        let weapon = app.world_mut().spawn(swing(Some(slow))).id();
+        let finished = app.world_mut().spawn(swing(None)).id();
+
+        app.world_mut().despawn(weapon);
+        app.world_mut().entity_mut(finished).remove::<MeleeSwing>();
+        assert_eq!(app.world().resource::<Events<DeleteStatMod>>().len(), 1);
+    }
+}
@@ -1,10 +1,15 @@
 use bevy::{prelude::Component, reflect::Reflect};
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
 
+pub mod cycle;
+pub mod dash;
+pub mod heal;
+pub mod hitscan;
 pub mod kill_self;
+pub mod melee;
 pub mod oneshot;
+pub mod sequence;
 pub mod spawn;
-pub mod vel_spawn;
 
 #[derive(Component, Reflect, Debug, Clone, Copy)]
 pub struct TelegraphedAction;
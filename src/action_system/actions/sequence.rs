@@ -0,0 +1,184 @@
+use bevy::{
+    app::{App, Update},
+    ecs::schedule::IntoSystemConfigs,
+    hierarchy::{Children, HierarchyQueryExt, Parent},
+    prelude::{Commands, Component, Entity, Query, Res, Transform, Trigger, With, Without},
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+
+use crate::{
+    action_system::{actuator::Actuate, ActuatorLogicPhases},
+    twin_stick::actors::Stunned,
+    util::add_observer_to_component,
+};
+
+/// Runs the actions on its `SequenceStep` children one after another instead of all at once. A
+/// sequence that `cancel_on_stun`s stops where it is if its owner gets stunned.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct ActionSequence {
+    pub cancel_on_stun: bool,
+}
+
+/// A child of an `ActionSequence` whose actions go off `delay` seconds after the step before it.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct SequenceStep {
+    pub index: usize,
+    pub delay: f32,
+}
+
+/// A sequence part way through, counting towards its `next` step.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct SequenceProgress {
+    pub next: usize,
+    pub waited: f32,
+}
+
+impl ActionSequence {
+    pub fn setup(app: &mut App) {
+        app.register_type::<ActionSequence>()
+            .register_type::<SequenceStep>()
+            .register_type::<SequenceProgress>();
+        app.add_systems(
+            Update,
+            advance_sequences.in_set(ActuatorLogicPhases::PostActuate),
+        );
+        app.add_observer(add_observer_to_component::<ActionSequence, _, _, _, _>(
+            do_sequence_action,
+        ));
+    }
+}
+
+/// `steps` as `(delay, actions)` pairs, run in order.
+pub fn sequence(steps: Vec<(f32, ComponentTree)>) -> ComponentTree {
+    steps.into_iter().enumerate().fold(
+        ActionSequence {
+            cancel_on_stun: true,
+        }
+        .store(),
+        |tree, (index, (delay, actions))| {
+            tree << (actions + (SequenceStep { index, delay }, Transform::default()).store())
+        },
+    )
+}
+
+pub fn do_sequence_action(
+    trigger: Trigger<Actuate>,
+    sequences: Query<(), (With<ActionSequence>, Without<SequenceProgress>)>,
+    mut commands: Commands,
+) {
+    // A sequence can't be started again until the last run is over.
+    if sequences.contains(trigger.entity()) {
+        commands.entity(trigger.entity()).insert(SequenceProgress {
+            next: 0,
+            waited: 0.,
+        });
+    }
+}
+
+pub fn advance_sequences(
+    mut sequences: Query<(Entity, &ActionSequence, &mut SequenceProgress, &Children)>,
+    steps: Query<&SequenceStep>,
+    stunned: Query<(), With<Stunned>>,
+    parents: Query<&Parent>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, sequence, mut progress, children) in sequences.iter_mut() {
+        if sequence.cancel_on_stun
+            && std::iter::once(entity)
+                .chain(parents.iter_ancestors(entity))
+                .any(|w| stunned.contains(w))
+        {
+            commands.entity(entity).remove::<SequenceProgress>();
+            continue;
+        }
+
+        let mut ordered: Vec<(Entity, &SequenceStep)> = children
+            .iter()
+            .filter_map(|w| steps.get(*w).ok().map(|step| (*w, step)))
+            .collect();
+        ordered.sort_by_key(|w| w.1.index);
+
+        progress.waited += time.delta().as_secs_f32();
+        while let Some((step, SequenceStep { delay, .. })) = ordered.get(progress.next) {
+            if progress.waited < *delay {
+                break;
+            }
+            progress.waited -= delay;
+            progress.next += 1;
+            commands.trigger_targets(Actuate, *step);
+        }
+        if progress.next >= ordered.len() {
+            commands.entity(entity).remove::<SequenceProgress>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{hierarchy::BuildChildren, math::Vec2, prelude::Events};
+
+    use super::*;
+    use crate::twin_stick::{
+        actors::Actor,
+        events::AttackEvent,
+        weapons::{stun_from_attacks, FriendlyFire, StunOnHit},
+    };
+
+    #[test]
+    fn a_stunning_hit_cancels_a_sequence() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<FriendlyFire>()
+            .add_event::<AttackEvent>()
+            .add_systems(Update, (stun_from_attacks, advance_sequences).chain());
+        ActionSequence::setup(&mut app);
+
+        let owner = app
+            .world_mut()
+            .spawn(Actor {
+                desired_direction: Vec2::ZERO,
+                desired_target: None,
+            })
+            .id();
+        let sequence = app
+            .world_mut()
+            .spawn(ActionSequence {
+                cancel_on_stun: true,
+            })
+            .set_parent(owner)
+            .with_children(|steps| {
+                steps.spawn(SequenceStep {
+                    index: 0,
+                    delay: 0.,
+                });
+                steps.spawn(SequenceStep {
+                    index: 1,
+                    delay: 1.,
+                });
+            })
+            .id();
+
+        app.world_mut().trigger_targets(Actuate, sequence);
+        app.update();
+        let progress = app.world().get::<SequenceProgress>(sequence).unwrap();
+        assert_eq!(progress.next, 1);
+
+        let weapon = app.world_mut().spawn(StunOnHit(0.4)).id();
+        app.world_mut()
+            .resource_mut::<Events<AttackEvent>>()
+            .send(AttackEvent {
+                attacker: weapon,
+                weapon,
+                defender: owner,
+                location: Vec2::ZERO,
+                direction: Vec2::X,
+                scale: 1.,
+            });
+        app.update();
+        assert!(app.world().get::<Stunned>(owner).is_some());
+        assert!(app.world().get::<SequenceProgress>(sequence).is_none());
+    }
+}
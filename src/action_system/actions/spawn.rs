@@ -1,50 +1,110 @@
+use avian2d::prelude::{ExternalImpulse, LinearVelocity};
 use bevy::{
-    app::App,
+    app::{App, Update},
+    color::palettes::css::ORANGE,
     ecs::{
         entity::Entity,
-        query::{Or, With},
+        query::{Has, Or, With},
+        system::SystemParam,
     },
-    hierarchy::{HierarchyQueryExt, Parent},
-    prelude::{Commands, Component, Query, Transform, Trigger},
+    hierarchy::{BuildChildren, HierarchyQueryExt, Parent},
+    math::{Quat, Vec2, Vec3Swizzles},
+    prelude::{Commands, Component, Gizmos, Query, ResMut, Transform, Trigger},
+    reflect::Reflect,
     transform::components::GlobalTransform,
 };
 use bevy_composable::{
     app_impl::{ComplexSpawnable, ComponentTreeable},
     tree::ComponentTree,
 };
+use bevy_stats::Stat;
+use bevy_turborand::{DelegatedRng, GlobalRng};
+use std::f32;
 
 use crate::{
-    action_system::actuator::Actuate,
-    twin_stick::{actors::Actor, weapons::Weapon},
+    action_system::{actions::TelegraphedAction, actuator::Actuate, triggers::timer::TimerTrigger},
+    game::stats::ProjectileSpeed,
+    transform2d::To2D,
+    twin_stick::{
+        actors::{Actor, Faction},
+        weapons::{faction_of, Weapon},
+    },
     util::add_observer_to_component,
 };
 
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub struct AngleOffset(pub Vec2);
+
+impl AngleOffset {
+    pub fn new(vec: Vec2) -> Self {
+        Self(vec.normalize())
+    }
+}
+
+/// Spawns each tree in `payload` where the entity is, turned by its angle offset. How it spawns
+/// them is up to the modifiers next to it: `Launch`, `Spread`, `InheritVelocity`, `SpawnOffset`
+/// and `SpawnAttached`.
 #[derive(Component, Clone)]
 pub struct SpawnAction {
-    pub payload: Vec<ComponentTree>,
+    pub payload: Vec<(ComponentTree, AngleOffset)>,
 }
 
 #[derive(Component, Clone, PartialEq, Hash, Debug)]
 pub struct SpawnedBy(pub Entity);
 
+/// Sends whatever is spawned off along its facing at the spawner's `ProjectileSpeed`.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Launch;
+
+/// Turns each spawn by a random angle of up to half this many radians either way.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Spread(pub f32);
+
+/// Adds this much of the owner's velocity to whatever is spawned.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct InheritVelocity(pub f32);
+
+/// Spawns this far from the spawner, in its own frame, rather than right on top of it.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct SpawnOffset(pub Vec2);
+
+/// Spawns as a child of the spawner, so it moves with it, rather than loose in the world.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct SpawnAttached;
+
+/// How fast a `Launch` sends things off when the spawner has no `ProjectileSpeed`.
+const DEFAULT_LAUNCH_SPEED: f32 = 10.0;
+
 impl SpawnAction {
     pub fn spawn(tree: ComponentTree) -> Self {
         Self {
-            payload: vec![tree],
+            payload: vec![(tree, 0.0.into())],
         }
     }
 
     pub fn spawns<T: Iterator<Item = ComponentTree>>(trees: T) -> Self {
         Self {
-            payload: trees.collect(),
+            payload: trees.map(|w| (w, 0.0.into())).collect(),
+        }
+    }
+
+    pub fn angled<A: Into<AngleOffset>, T: Iterator<Item = (ComponentTree, A)>>(trees: T) -> Self {
+        Self {
+            payload: trees.map(|w| (w.0, w.1.into())).collect(),
         }
     }
 
     pub fn setup(app: &mut App) {
         // app.register_type::<SpawnAction>();
+        app.register_type::<Launch>()
+            .register_type::<Spread>()
+            .register_type::<InheritVelocity>()
+            .register_type::<SpawnOffset>()
+            .register_type::<SpawnAttached>();
         app.add_observer(add_observer_to_component::<SpawnAction, _, _, _, _>(
             do_spawn_action,
         ));
+        app.add_systems(Update, display_telegraphed_launches);
     }
 }
 
@@ -56,33 +116,173 @@ pub fn spawns<T: Iterator<Item = ComponentTree>>(trees: T) -> ComponentTree {
     SpawnAction::spawns(trees).store()
 }
 
+/// Fires `tree` off at `angle` from the spawner's facing.
+pub fn vel_spawn<T: Into<AngleOffset>>(tree: ComponentTree, angle: T) -> ComponentTree {
+    SpawnAction::angled(std::iter::once((tree, angle))).store() + launch()
+}
+
+pub fn vel_spawns<A: Into<AngleOffset>, T: Iterator<Item = (ComponentTree, A)>>(
+    trees: T,
+) -> ComponentTree {
+    SpawnAction::angled(trees).store() + launch()
+}
+
+pub fn launch() -> ComponentTree {
+    Launch.store()
+}
+
+pub fn spread(angle: f32) -> ComponentTree {
+    Spread(angle).store()
+}
+
+pub fn inherit_velocity(factor: f32) -> ComponentTree {
+    InheritVelocity(factor).store()
+}
+
+pub fn spawn_offset(x: f32, y: f32) -> ComponentTree {
+    SpawnOffset(Vec2::new(x, y)).store()
+}
+
+pub fn attached() -> ComponentTree {
+    SpawnAttached.store()
+}
+
+/// Everything a spawn needs to know about the spawner's surroundings.
+#[derive(SystemParam)]
+pub struct SpawnQueries<'w, 's> {
+    attackers: Query<'w, 's, Entity, Or<(With<Actor>, With<Weapon>)>>,
+    factions: Query<'w, 's, &'static Faction>,
+    parents: Query<'w, 's, &'static Parent>,
+    velocities: Query<'w, 's, &'static LinearVelocity>,
+    rng: ResMut<'w, GlobalRng>,
+}
+
+/// Marks `spawned` as coming from `attacker` and puts it on the attacker's side.
+fn inherit_from(
+    commands: &mut Commands,
+    spawned: Entity,
+    attacker: Entity,
+    queries: &SpawnQueries,
+) {
+    let mut spawned = commands.entity(spawned);
+    spawned.insert(SpawnedBy(attacker));
+    if let Some(faction) = faction_of(attacker, &queries.factions, &queries.parents) {
+        spawned.insert(faction);
+    }
+}
+
 pub fn do_spawn_action(
     trigger: Trigger<Actuate>,
-    spawners: Query<(Entity, &SpawnAction, &GlobalTransform)>,
-    attackers: Query<Entity, Or<(With<Actor>, With<Weapon>)>>,
-    parents: Query<&Parent>,
+    spawners: Query<(
+        &SpawnAction,
+        &GlobalTransform,
+        Option<&Stat<ProjectileSpeed>>,
+        (
+            Has<Launch>,
+            Option<&Spread>,
+            Option<&InheritVelocity>,
+            Option<&SpawnOffset>,
+            Has<SpawnAttached>,
+        ),
+    )>,
+    mut queries: SpawnQueries,
     mut commands: Commands,
 ) {
-    if let Ok((e, spawn_action, transform)) = spawners.get(trigger.entity()) {
-        for payload in spawn_action.payload.iter() {
-            let (scale, rotation, translation) = transform.to_scale_rotation_translation();
-            let spawned_transform = Transform {
-                translation,
-                rotation,
+    let e = trigger.entity();
+    let Ok((spawn_action, transform, speed, (launch, spread, inherit, offset, attach))) =
+        spawners.get(e)
+    else {
+        return;
+    };
+    let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+    let offset = offset.map(|w| w.0).unwrap_or(Vec2::ZERO);
+    // If there's a first ancestor with Weapon/Actor
+    let attacker = std::iter::once(e)
+        .chain(queries.parents.iter_ancestors(e))
+        .find(|w| queries.attackers.contains(*w));
+    let owner_velocity = inherit.and_then(|w| {
+        std::iter::once(e)
+            .chain(queries.parents.iter_ancestors(e))
+            .find_map(|e| queries.velocities.get(e).ok())
+            .map(|velocity| velocity.0 * w.0)
+    });
+
+    for (payload, angle_offset) in spawn_action.payload.iter() {
+        let mut angle = angle_offset.0.to_angle();
+        if let Some(Spread(spread)) = spread {
+            angle += queries.rng.f32_normalized() * spread / 2.;
+        }
+        let spawned_transform = if attach {
+            Transform::from_translation(offset.extend(0.)).with_rotation(Quat::from_2d(angle))
+        } else {
+            Transform {
+                translation: translation + rotation * offset.extend(0.),
+                rotation: rotation * Quat::from_2d(angle),
                 scale,
-            };
-
-            if let Some(attacker) = std::iter::once(e)
-                .chain(parents.iter_ancestors(e))
-                .filter(|w| attackers.get(*w).is_ok())
-                .next()
-            // If there's a first ancestor with Weapon/Actor
-            {
-                commands
-                    .compose(payload.clone() + (spawned_transform, SpawnedBy(attacker)).store());
-            } else {
-                commands.compose(payload.clone() + spawned_transform.store());
             }
+        };
+        let spawned = commands.compose(payload.clone() + spawned_transform.store());
+
+        if attach {
+            commands.entity(spawned).set_parent(e);
+        }
+        if launch {
+            let direction = Vec2::from_angle(rotation.to_2d() + angle + f32::consts::FRAC_PI_2);
+            commands.entity(spawned).insert(ExternalImpulse::new(
+                direction
+                    * speed
+                        .map(|w| w.current_value())
+                        .unwrap_or(DEFAULT_LAUNCH_SPEED),
+            ));
         }
+        if let Some(velocity) = owner_velocity {
+            commands.entity(spawned).insert(LinearVelocity(velocity));
+        }
+        if let Some(attacker) = attacker {
+            inherit_from(&mut commands, spawned, attacker, &queries);
+        }
+    }
+}
+
+/// Draws where each shot of a telegraphed launcher will go, reaching further as its timer runs
+/// down.
+pub fn display_telegraphed_launches(
+    spawners: Query<
+        (&SpawnAction, &GlobalTransform, Option<&TimerTrigger>),
+        (With<Launch>, With<TelegraphedAction>),
+    >,
+    mut gizmos: Gizmos,
+) {
+    for (spawn_action, transform, timer) in spawners.iter() {
+        let (_, rotation, translation) = transform.to_scale_rotation_translation();
+        let length = 40. + 160. * timer.map(|w| w.timer.fraction()).unwrap_or(1.);
+        for (_, angle_offset) in spawn_action.payload.iter() {
+            let direction = Vec2::from_angle(
+                rotation.to_2d() + angle_offset.0.to_angle() + f32::consts::FRAC_PI_2,
+            );
+            gizmos.line_2d(
+                translation.xy(),
+                translation.xy() + direction * length,
+                ORANGE,
+            );
+        }
+    }
+}
+
+impl Into<AngleOffset> for Vec2 {
+    fn into(self) -> AngleOffset {
+        AngleOffset(self)
+    }
+}
+
+impl Into<AngleOffset> for f32 {
+    fn into(self) -> AngleOffset {
+        AngleOffset(Vec2::from_angle(self))
+    }
+}
+
+impl Into<AngleOffset> for Quat {
+    fn into(self) -> AngleOffset {
+        self.to_2d().into()
     }
 }
@@ -1,15 +1,29 @@
-use actions::{oneshot::OneShotAction, spawn::SpawnAction, vel_spawn::VelSpawnAction};
+use actions::{
+    cycle::ActionCycle, dash::DashAction, heal::HealAction, hitscan::HitscanAction,
+    melee::MeleeAction, oneshot::OneShotAction, sequence::ActionSequence, spawn::SpawnAction,
+};
 use actuator::Actuator;
 use bevy::{
     app::{Plugin, Update},
     ecs::schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
     reflect::Reflect,
+    state::condition::in_state,
 };
 use triggers::{
-    key_action::PlayerActionTrigger, propagation::ParentTrigger, proximity::ProximityTrigger,
+    counter::CounterTrigger,
+    key_action::PlayerActionTrigger,
+    logic::TriggerGate,
+    propagation::ParentTrigger,
+    proximity::ProximityTrigger,
+    threshold::{ResourceThreshold, StatThreshold},
     timer::TimerTrigger,
 };
 
+use crate::{
+    game::stats::{Accuracy, Damage, Health, Knockback, MoveSpeed, ProjectileSpeed},
+    states::TimerState,
+};
+
 pub mod actions;
 pub mod actuator;
 pub mod prefabs;
@@ -32,10 +46,23 @@ impl Plugin for ActionSystemPlugin {
         ProximityTrigger::setup(app);
         PlayerActionTrigger::setup(app);
         ParentTrigger::setup(app);
+        TriggerGate::setup(app);
+        CounterTrigger::setup(app);
+        ResourceThreshold::<Health>::setup(app);
+        StatThreshold::<MoveSpeed>::setup(app);
+        StatThreshold::<Damage>::setup(app);
+        StatThreshold::<Knockback>::setup(app);
+        StatThreshold::<ProjectileSpeed>::setup(app);
+        StatThreshold::<Accuracy>::setup(app);
 
         SpawnAction::setup(app);
         OneShotAction::setup(app);
-        VelSpawnAction::setup(app);
+        DashAction::setup(app);
+        HealAction::setup(app);
+        MeleeAction::setup(app);
+        HitscanAction::setup(app);
+        ActionSequence::setup(app);
+        ActionCycle::setup(app);
 
         app.configure_sets(
             Update,
@@ -43,7 +70,8 @@ impl Plugin for ActionSystemPlugin {
                 ActuatorLogicPhases::PreActuate,
                 ActuatorLogicPhases::Actuate.after(ActuatorLogicPhases::PreActuate),
                 ActuatorLogicPhases::PostActuate.after(ActuatorLogicPhases::Actuate),
-            ),
+            )
+                .run_if(in_state(TimerState::Playing)),
         );
     }
 }
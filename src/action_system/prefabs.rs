@@ -1,7 +1,10 @@
 use bevy_composable::tree::ComponentTree;
 
 use super::{
-    actions::{oneshot::oneshot, spawn::spawn},
+    actions::{
+        oneshot::oneshot,
+        spawn::{spawn, spawns},
+    },
     actuator::{actuator, ActuatorFireStyle},
     triggers::{proximity::proximity, timer::timer},
 };
@@ -16,3 +19,14 @@ pub fn spawn_prox(factions: u16, radius: f32, tree: ComponentTree) -> ComponentT
         + spawn(tree)
         + oneshot()
 }
+
+pub fn spawn_prox_many<T: Iterator<Item = ComponentTree>>(
+    factions: u16,
+    radius: f32,
+    trees: T,
+) -> ComponentTree {
+    actuator(ActuatorFireStyle::RisingEdge, 0.5)
+        + proximity(factions, radius)
+        + spawns(trees)
+        + oneshot()
+}
@@ -0,0 +1,119 @@
+use bevy::{
+    app::{App, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Added, Has, With},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query},
+    },
+    hierarchy::{Children, Parent},
+    prelude::Trigger,
+    reflect::Reflect,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+
+use crate::{
+    action_system::{
+        actuator::{Actuate, ActuatorCondition},
+        ActuatorLogicPhases,
+    },
+    util::add_observer_to_component,
+};
+
+/// Counts the times its parent's actuator fires, and comes on for every `every`th of them until
+/// the next one, e.g. to make every third shot a rocket.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct CounterTrigger {
+    pub every: usize,
+    pub count: usize,
+}
+
+/// An actuator with `CounterTrigger` children keeping count of it.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct CountedActuator;
+
+impl CounterTrigger {
+    pub fn new(every: usize) -> Self {
+        Self { every, count: 0 }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.register_type::<CounterTrigger>()
+            .register_type::<CountedActuator>();
+        app.add_systems(
+            Update,
+            watch_counted_actuators.in_set(ActuatorLogicPhases::PreActuate),
+        );
+        app.add_observer(add_observer_to_component::<CountedActuator, _, _, _, _>(
+            count_actuations,
+        ));
+    }
+}
+
+pub fn counter(every: usize) -> ComponentTree {
+    CounterTrigger::new(every).store()
+}
+
+pub fn watch_counted_actuators(
+    counters: Query<&Parent, Added<CounterTrigger>>,
+    counted: Query<(), With<CountedActuator>>,
+    mut commands: Commands,
+) {
+    for parent in counters.iter() {
+        if !counted.contains(parent.get()) {
+            commands.entity(parent.get()).insert(CountedActuator);
+        }
+    }
+}
+
+pub fn count_actuations(
+    trigger: Trigger<Actuate>,
+    parents: Query<&Children, With<CountedActuator>>,
+    mut counters: Query<(Entity, &mut CounterTrigger, Has<ActuatorCondition>)>,
+    mut commands: Commands,
+) {
+    let Ok(children) = parents.get(trigger.entity()) else {
+        return;
+    };
+    for (entity, mut counter, active) in children.iter().filter_map(|w| counters.get_mut(*w).ok()) {
+        counter.count += 1;
+        if counter.count >= counter.every.max(1) {
+            counter.count = 0;
+            commands.entity(entity).insert(ActuatorCondition);
+        } else if active {
+            commands.entity(entity).remove::<ActuatorCondition>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::hierarchy::BuildChildren;
+
+    use super::*;
+
+    #[test]
+    fn on_for_every_third_shot() {
+        let mut app = App::new();
+        CounterTrigger::setup(&mut app);
+        let mut counter = Entity::PLACEHOLDER;
+        let actuator = app
+            .world_mut()
+            .spawn_empty()
+            .with_children(|children| {
+                counter = children.spawn(CounterTrigger::new(3)).id();
+            })
+            .id();
+        app.update();
+
+        let on: Vec<bool> = (0..7)
+            .map(|_| {
+                app.world_mut().trigger_targets(Actuate, actuator);
+                app.world_mut().flush();
+                app.world().get::<ActuatorCondition>(counter).is_some()
+            })
+            .collect();
+        assert_eq!(on, [false, false, true, false, false, true, false]);
+    }
+}
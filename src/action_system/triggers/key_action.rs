@@ -3,6 +3,7 @@ use bevy::{
     ecs::{
         component::Component,
         query::Changed,
+        schedule::IntoSystemConfigs,
         system::{Commands, Query},
     },
     hierarchy::Children,
@@ -12,7 +13,10 @@ use bevy::{
 use leafwing_input_manager::prelude::ActionState;
 use strum::IntoEnumIterator;
 
-use crate::{action_system::actuator::ActuatorCondition, twin_stick::ai::keyboard::PlayerAction};
+use crate::{
+    action_system::{actuator::ActuatorCondition, ActuatorLogicPhases},
+    twin_stick::ai::keyboard::PlayerAction,
+};
 
 #[derive(Component, Reflect, Clone, Debug)]
 pub struct PlayerActionTrigger {
@@ -30,7 +34,10 @@ impl PlayerActionTrigger {
 
     pub fn setup(app: &mut App) {
         app.register_type::<PlayerActionTrigger>();
-        app.add_systems(Update, sync_playeraction_triggers);
+        app.add_systems(
+            Update,
+            sync_playeraction_triggers.in_set(ActuatorLogicPhases::PreActuate),
+        );
     }
 }
 
@@ -0,0 +1,223 @@
+use bevy::{
+    app::{App, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::{Has, With},
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query},
+    },
+    hierarchy::Children,
+    prelude::Trigger,
+    reflect::Reflect,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+
+use crate::{
+    action_system::{
+        actuator::{ActuatorCondition, ActuatorCooldownFinished},
+        ActuatorLogicPhases,
+    },
+    util::add_observer_to_component,
+};
+
+/// How a `TriggerGate` turns the conditions of its inputs into its own.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GateLogic {
+    And,
+    Or,
+    /// On while none of the inputs are.
+    Not,
+    /// On while an odd number of the inputs are.
+    Xor,
+    /// On for a single frame whenever any input comes on.
+    Edge,
+    /// Comes on with the first input and stays on until the second.
+    Latch,
+}
+
+/// Sets the entity's `ActuatorCondition` from the conditions of its `GateInput` children, which
+/// can be any trigger, `ParentTrigger`s or other gates.
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+pub struct TriggerGate {
+    pub logic: GateLogic,
+    /// Whether any input was on last frame, for `Edge`, or whether a `Latch` is set.
+    pub state: bool,
+}
+
+/// A trigger feeding the `TriggerGate` on its parent. It doesn't need an `Actuator` of its own.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GateInput(pub usize);
+
+impl TriggerGate {
+    pub fn new(logic: GateLogic) -> Self {
+        Self {
+            logic,
+            state: false,
+        }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.register_type::<TriggerGate>()
+            .register_type::<GateInput>();
+        app.add_systems(
+            Update,
+            evaluate_gates.in_set(ActuatorLogicPhases::PreActuate),
+        );
+        app.add_observer(add_observer_to_component::<TriggerGate, _, _, _, _>(
+            reset_gate_inputs,
+        ));
+    }
+
+    fn evaluate(&mut self, inputs: &[bool]) -> bool {
+        let any = inputs.iter().any(|w| *w);
+        match self.logic {
+            GateLogic::And => !inputs.is_empty() && inputs.iter().all(|w| *w),
+            GateLogic::Or => any,
+            GateLogic::Not => !any,
+            GateLogic::Xor => inputs.iter().filter(|w| **w).count() % 2 == 1,
+            GateLogic::Edge => {
+                let rising = any && !self.state;
+                self.state = any;
+                rising
+            }
+            GateLogic::Latch => {
+                if inputs.get(1).copied().unwrap_or(false) {
+                    self.state = false;
+                } else if inputs.first().copied().unwrap_or(false) {
+                    self.state = true;
+                }
+                self.state
+            }
+        }
+    }
+}
+
+/// A gate over `inputs`, which are numbered in the order given.
+pub fn gate(logic: GateLogic, inputs: Vec<ComponentTree>) -> ComponentTree {
+    inputs
+        .into_iter()
+        .enumerate()
+        .fold(TriggerGate::new(logic).store(), |tree, (index, input)| {
+            tree << (input + GateInput(index).store())
+        })
+}
+
+pub fn all_of(inputs: Vec<ComponentTree>) -> ComponentTree {
+    gate(GateLogic::And, inputs)
+}
+
+pub fn any_of(inputs: Vec<ComponentTree>) -> ComponentTree {
+    gate(GateLogic::Or, inputs)
+}
+
+pub fn none_of(inputs: Vec<ComponentTree>) -> ComponentTree {
+    gate(GateLogic::Not, inputs)
+}
+
+pub fn evaluate_gates(
+    mut gates: Query<(Entity, &mut TriggerGate, &Children, Has<ActuatorCondition>)>,
+    inputs: Query<(&GateInput, Has<ActuatorCondition>)>,
+    mut commands: Commands,
+) {
+    for (entity, mut gate, children, active) in gates.iter_mut() {
+        let mut states: Vec<(usize, bool)> = children
+            .iter()
+            .filter_map(|w| inputs.get(*w).ok())
+            .map(|(GateInput(index), on)| (*index, on))
+            .collect();
+        states.sort_by_key(|w| w.0);
+        let states: Vec<bool> = states.into_iter().map(|w| w.1).collect();
+
+        let on = gate.evaluate(&states);
+        if on && !active {
+            commands.entity(entity).insert(ActuatorCondition);
+        } else if !on && active {
+            commands.entity(entity).remove::<ActuatorCondition>();
+        }
+    }
+}
+
+/// Inputs have no actuator of their own, so they reset (e.g. timers restart) with the gate's.
+pub fn reset_gate_inputs(
+    trigger: Trigger<ActuatorCooldownFinished>,
+    gates: Query<&Children, With<TriggerGate>>,
+    inputs: Query<(), With<GateInput>>,
+    mut commands: Commands,
+) {
+    let Ok(children) = gates.get(trigger.entity()) else {
+        return;
+    };
+    for input in children.iter().filter(|w| inputs.contains(**w)) {
+        commands.trigger_targets(ActuatorCooldownFinished, *input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `frames` of inputs through a fresh gate and collects what it says each frame.
+    fn run(logic: GateLogic, frames: &[&[bool]]) -> Vec<bool> {
+        let mut gate = TriggerGate::new(logic);
+        frames.iter().map(|w| gate.evaluate(w)).collect()
+    }
+
+    #[test]
+    fn combinational_gates() {
+        let frames: &[&[bool]] = &[
+            &[],
+            &[false, false],
+            &[true, false],
+            &[false, true],
+            &[true, true],
+            &[true, true, true],
+        ];
+        for (logic, expected) in [
+            (GateLogic::And, [false, false, false, false, true, true]),
+            (GateLogic::Or, [false, false, true, true, true, true]),
+            (GateLogic::Not, [true, true, false, false, false, false]),
+            (GateLogic::Xor, [false, false, true, true, false, true]),
+        ] {
+            assert_eq!(run(logic, frames), expected, "{logic:?}");
+        }
+    }
+
+    #[test]
+    fn edge_fires_once_per_rise() {
+        assert_eq!(
+            run(
+                GateLogic::Edge,
+                &[
+                    &[false],
+                    &[true],
+                    &[true],
+                    &[false],
+                    &[true],
+                    &[true, true],
+                    &[false, true],
+                ]
+            ),
+            [false, true, false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn latch_holds_until_reset_and_reset_wins() {
+        assert_eq!(
+            run(
+                GateLogic::Latch,
+                &[
+                    &[false, false],
+                    &[true, false],
+                    &[false, false],
+                    &[true, true],
+                    &[false, false],
+                    &[true],
+                    &[false, true],
+                ]
+            ),
+            [false, true, true, false, false, true, false]
+        );
+    }
+}
@@ -1,4 +1,7 @@
+pub mod counter;
 pub mod key_action;
+pub mod logic;
 pub mod propagation;
 pub mod proximity;
+pub mod threshold;
 pub mod timer;
@@ -5,13 +5,16 @@ use bevy::{
         entity::Entity,
         query::{Added, With, Without},
         removal_detection::RemovedComponents,
+        schedule::IntoSystemConfigs,
         system::{Commands, Query},
     },
     hierarchy::Children,
     reflect::Reflect,
 };
 
-use crate::action_system::actuator::ActuatorCondition;
+use crate::action_system::{actuator::ActuatorCondition, ActuatorLogicPhases};
+
+use super::logic::{GateInput, TriggerGate};
 
 #[derive(Component, Reflect, Clone, Debug)]
 pub struct ParentTrigger;
@@ -19,18 +22,41 @@ pub struct ParentTrigger;
 impl ParentTrigger {
     pub fn setup(app: &mut App) {
         app.register_type::<ParentTrigger>();
-        app.add_systems(Update, (trigger_with_parent, untrigger_with_parent));
+        app.add_systems(
+            Update,
+            (trigger_with_parent, untrigger_with_parent).in_set(ActuatorLogicPhases::PreActuate),
+        );
     }
 }
 
+/// The entities under `children` that follow their parent. A gate's inputs follow the gate's
+/// parent rather than the gate, so a gate can sit where a `ParentTrigger` would.
+fn followers<'a>(
+    children: &'a Children,
+    gates: &'a Query<&Children, With<TriggerGate>>,
+) -> impl Iterator<Item = Entity> + 'a {
+    children.iter().flat_map(move |w| {
+        std::iter::once(*w).chain(
+            gates
+                .get(*w)
+                .into_iter()
+                .flat_map(|inputs| inputs.iter().copied()),
+        )
+    })
+}
+
 pub fn trigger_with_parent(
     parents: Query<&Children, Added<ActuatorCondition>>,
+    gates: Query<&Children, With<TriggerGate>>,
     triggers: Query<Entity, (With<ParentTrigger>, Without<ActuatorCondition>)>,
+    inputs: Query<(), With<GateInput>>,
     mut commands: Commands,
 ) {
     for children in parents.iter() {
-        let children = children.iter().filter_map(|e| triggers.get(*e).ok());
-        for child in children {
+        let triggered = followers(children, &gates)
+            .filter(|w| !(inputs.contains(*w) && children.contains(w)))
+            .filter_map(|e| triggers.get(e).ok());
+        for child in triggered {
             commands
                 .get_entity(child)
                 .unwrap()
@@ -42,11 +68,16 @@ pub fn trigger_with_parent(
 pub fn untrigger_with_parent(
     mut removed: RemovedComponents<ActuatorCondition>,
     parents: Query<&Children, Without<ActuatorCondition>>,
+    gates: Query<&Children, With<TriggerGate>>,
     triggers: Query<Entity, (With<ParentTrigger>, With<ActuatorCondition>)>,
+    inputs: Query<(), With<GateInput>>,
     mut commands: Commands,
 ) {
     for children in removed.read().filter_map(|w| parents.get(w).ok()) {
-        for child in children.iter().filter_map(|w| triggers.get(*w).ok()) {
+        let untriggered = followers(children, &gates)
+            .filter(|w| !(inputs.contains(*w) && children.contains(w)))
+            .filter_map(|w| triggers.get(w).ok());
+        for child in untriggered {
             commands
                 .get_entity(child)
                 .unwrap()
@@ -1,17 +1,23 @@
 use bevy::{
     app::{App, Update},
     color::palettes::css::BLUE,
+    ecs::schedule::IntoSystemConfigs,
     math::Vec3Swizzles,
-    prelude::{Commands, Component, Entity, Gizmos, Query, Transform, With, Without},
+    prelude::{
+        Commands, Component, Entity, Gizmos, GlobalTransform, Or, Parent, Query, Transform, With,
+        Without,
+    },
     reflect::Reflect,
 };
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
 use core::f32;
 
+use super::logic::GateInput;
 use crate::{
     action_system::{
         actions::TelegraphedAction,
         actuator::{Actuator, ActuatorCondition},
+        ActuatorLogicPhases,
     },
     twin_stick::actors::Faction,
 };
@@ -39,7 +45,7 @@ impl ProximityTrigger {
         app.add_systems(
             Update,
             (
-                activate_deactivate_proximity_triggers,
+                activate_deactivate_proximity_triggers.in_set(ActuatorLogicPhases::PreActuate),
                 display_prox_triggers,
             ),
         );
@@ -51,23 +57,31 @@ pub fn activate_deactivate_proximity_triggers(
         (
             Entity,
             &Transform,
+            &GlobalTransform,
+            Option<&Parent>,
             &ProximityTrigger,
             Option<&ActuatorCondition>,
         ),
-        With<Actuator>,
+        Or<(With<Actuator>, With<GateInput>)>,
     >,
     triggering_entities: Query<(&Transform, &Faction), Without<ProximityTrigger>>,
     mut commands: Commands,
 ) {
     let (already_deactivated, already_activated): (Vec<_>, Vec<_>) = prox_query
         .iter()
-        .map(|(entity, transform, trigger, option)| {
+        .map(|(entity, transform, global, parent, trigger, option)| {
+            // Triggers carried by an actor only know their offset from it, so measure those from
+            // their world position instead.
+            let position = match parent {
+                Some(_) => global.translation().xy(),
+                None => transform.translation.xy(),
+            };
             (
                 entity,
                 triggering_entities
                     .iter()
                     .filter(|(_, fac)| ((1 << fac.0) & trigger.triggering_factions) != 0)
-                    .map(|(t, _)| t.translation.xy().distance(transform.translation.xy()))
+                    .map(|(t, _)| t.translation.xy().distance(position))
                     .fold(f32::INFINITY, |a, b| a.min(b)),
                 trigger,
                 option,
@@ -0,0 +1,220 @@
+use bevy::{
+    app::{App, Update},
+    ecs::{
+        component::Component,
+        entity::Entity,
+        query::Has,
+        schedule::IntoSystemConfigs,
+        system::{Commands, Query},
+    },
+    hierarchy::{HierarchyQueryExt, Parent},
+    reflect::Reflect,
+};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::{RPGResource, RPGStat, Resource, Stat};
+use std::marker::PhantomData;
+
+use crate::{
+    action_system::{actuator::ActuatorCondition, ActuatorLogicPhases},
+    game::stats::ResourceMax,
+};
+
+/// Which side of its threshold a value has to be on for the trigger to be on.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crossing {
+    Below,
+    Above,
+}
+
+/// A value to watch for crossing `value`.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct Threshold {
+    pub value: f32,
+    pub crossing: Crossing,
+}
+
+impl Threshold {
+    pub fn below(value: f32) -> Self {
+        Self {
+            value,
+            crossing: Crossing::Below,
+        }
+    }
+
+    pub fn above(value: f32) -> Self {
+        Self {
+            crossing: Crossing::Above,
+            ..Self::below(value)
+        }
+    }
+
+    fn holds(&self, current: f32) -> bool {
+        match self.crossing {
+            Crossing::Below => current <= self.value,
+            Crossing::Above => current >= self.value,
+        }
+    }
+}
+
+/// On while the nearest `Stat<T>` at or above the entity is across its threshold.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct StatThreshold<T: RPGStat> {
+    pub threshold: Threshold,
+    marker: PhantomData<T>,
+}
+
+/// On while the nearest `Resource<T>` at or above the entity, such as the owner's health, is across
+/// its threshold. A `relative` threshold is a fraction of the resource's `ResourceMax`, so 0.3 on
+/// health means 30% of full health.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ResourceThreshold<T: RPGResource> {
+    pub threshold: Threshold,
+    pub relative: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: RPGStat> StatThreshold<T> {
+    pub fn new(threshold: Threshold) -> Self {
+        Self {
+            threshold,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.add_systems(
+            Update,
+            check_stat_thresholds::<T>.in_set(ActuatorLogicPhases::PreActuate),
+        );
+    }
+}
+
+impl<T: RPGResource> ResourceThreshold<T> {
+    pub fn new(threshold: Threshold) -> Self {
+        Self {
+            threshold,
+            relative: false,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn relative(threshold: Threshold) -> Self {
+        Self {
+            relative: true,
+            ..Self::new(threshold)
+        }
+    }
+
+    pub fn setup(app: &mut App) {
+        app.add_systems(
+            Update,
+            check_resource_thresholds::<T>.in_set(ActuatorLogicPhases::PreActuate),
+        );
+    }
+}
+
+pub fn stat_threshold<T: RPGStat>(threshold: Threshold) -> ComponentTree {
+    StatThreshold::<T>::new(threshold).store()
+}
+
+pub fn resource_threshold<T: RPGResource>(threshold: Threshold) -> ComponentTree {
+    ResourceThreshold::<T>::new(threshold).store()
+}
+
+/// A threshold on the fraction of full the resource is at.
+pub fn relative_resource_threshold<T: RPGResource>(threshold: Threshold) -> ComponentTree {
+    ResourceThreshold::<T>::relative(threshold).store()
+}
+
+fn set_condition(commands: &mut Commands, entity: Entity, on: bool, active: bool) {
+    if on && !active {
+        commands.entity(entity).insert(ActuatorCondition);
+    } else if !on && active {
+        commands.entity(entity).remove::<ActuatorCondition>();
+    }
+}
+
+pub fn check_stat_thresholds<T: RPGStat>(
+    triggers: Query<(Entity, &StatThreshold<T>, Has<ActuatorCondition>)>,
+    stats: Query<&Stat<T>>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for (entity, trigger, active) in triggers.iter() {
+        let Some(stat) = std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find_map(|w| stats.get(w).ok())
+        else {
+            continue;
+        };
+        let on = trigger.threshold.holds(stat.current_value());
+        set_condition(&mut commands, entity, on, active);
+    }
+}
+
+pub fn check_resource_thresholds<T: RPGResource>(
+    triggers: Query<(Entity, &ResourceThreshold<T>, Has<ActuatorCondition>)>,
+    resources: Query<(&Resource<T>, Option<&ResourceMax<T>>)>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for (entity, trigger, active) in triggers.iter() {
+        let Some((resource, max)) = std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find_map(|w| resources.get(w).ok())
+        else {
+            continue;
+        };
+        let current = if trigger.relative {
+            // Without a known maximum there's nothing to be a fraction of, so stay off.
+            let Some(max) = max else {
+                set_condition(&mut commands, entity, false, active);
+                continue;
+            };
+            resource.current_value() / max.value.max(f32::EPSILON)
+        } else {
+            resource.current_value()
+        };
+        let on = trigger.threshold.holds(current);
+        set_condition(&mut commands, entity, on, active);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::hierarchy::BuildChildren;
+
+    use super::*;
+    use crate::game::stats::Health;
+
+    #[test]
+    fn relative_thresholds_use_the_full_value_not_the_current_one() {
+        let mut app = App::new();
+        ResourceThreshold::<Health>::setup(&mut app);
+        // An actor that was already down to 2 of 10 health when the triggers were attached.
+        let actor = app
+            .world_mut()
+            .spawn((Resource::<Health>::new(2.), ResourceMax::<Health>::new(10.)))
+            .id();
+        let enrage = app
+            .world_mut()
+            .spawn(ResourceThreshold::<Health>::relative(Threshold::below(0.3)))
+            .set_parent(actor)
+            .id();
+        let full = app
+            .world_mut()
+            .spawn(ResourceThreshold::<Health>::relative(Threshold::above(1.)))
+            .set_parent(actor)
+            .id();
+        let flat = app
+            .world_mut()
+            .spawn(ResourceThreshold::<Health>::new(Threshold::above(2.)))
+            .set_parent(actor)
+            .id();
+        app.update();
+
+        assert!(app.world().get::<ActuatorCondition>(enrage).is_some());
+        assert!(app.world().get::<ActuatorCondition>(full).is_none());
+        assert!(app.world().get::<ActuatorCondition>(flat).is_some());
+    }
+}
@@ -1,9 +1,11 @@
 use bevy::{
     app::{App, Update},
     color::palettes::css::RED,
+    ecs::schedule::IntoSystemConfigs,
     math::Vec3Swizzles,
     prelude::{
-        Changed, Commands, Component, Entity, Gizmos, Query, Res, Transform, Trigger, With, Without,
+        Changed, Commands, Component, Entity, Gizmos, GlobalTransform, Or, Query, Res, Trigger,
+        With, Without,
     },
     reflect::Reflect,
     time::{Time, Timer},
@@ -11,10 +13,12 @@ use bevy::{
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
 use std::time::Duration;
 
+use super::logic::GateInput;
 use crate::{
     action_system::{
         actions::TelegraphedAction,
         actuator::{Actuator, ActuatorCondition, ActuatorCooldownFinished},
+        ActuatorLogicPhases,
     },
     util::add_observer_to_component,
 };
@@ -47,10 +51,13 @@ impl TimerTrigger {
         app.add_systems(
             Update,
             (
-                tick_timer_triggers,
-                reset_immediate_timers,
-                activate_timer_triggers,
-                deactivate_timer_triggers,
+                (
+                    tick_timer_triggers,
+                    reset_immediate_timers,
+                    activate_timer_triggers,
+                    deactivate_timer_triggers,
+                )
+                    .in_set(ActuatorLogicPhases::PreActuate),
                 display_timer_triggers,
             ),
         );
@@ -78,7 +85,7 @@ pub fn activate_timer_triggers(
         (Entity, &TimerTrigger),
         (
             Without<ActuatorCondition>,
-            With<Actuator>,
+            Or<(With<Actuator>, With<GateInput>)>,
             Changed<TimerTrigger>,
         ),
     >,
@@ -114,7 +121,7 @@ pub fn deactivate_timer_triggers(
         (Entity, &TimerTrigger),
         (
             With<ActuatorCondition>,
-            With<Actuator>,
+            Or<(With<Actuator>, With<GateInput>)>,
             Changed<TimerTrigger>,
         ),
     >,
@@ -129,12 +136,12 @@ pub fn deactivate_timer_triggers(
 }
 
 pub fn display_timer_triggers(
-    timers: Query<(&TimerTrigger, &Transform), With<TelegraphedAction>>,
+    timers: Query<(&TimerTrigger, &GlobalTransform), With<TelegraphedAction>>,
     mut gizmos: Gizmos,
 ) {
     for (timer, transform) in timers.iter() {
         gizmos.circle_2d(
-            transform.translation.xy(),
+            transform.translation().xy(),
             (1. - timer.timer.fraction()) * 50.,
             RED,
         );
@@ -1,9 +1,17 @@
-use bevy::ecs::system::{Commands, Resource};
+use bevy::{
+    ecs::system::{Commands, Resource},
+    math::Vec2,
+};
 use bevy_composable::app_impl::ComplexSpawnable;
 
-use super::arena_objects::wall;
+use super::{
+    arena_objects::{tile_prefab, wall_collider, wall_tile},
+    generator::Cell,
+    merge::merge_walls,
+    tiles::Tile,
+};
 
-type ArenaMap = Vec<Vec<bool>>;
+pub type ArenaMap = Vec<Vec<Tile>>;
 
 #[derive(Resource)]
 pub struct Arena {
@@ -11,29 +19,66 @@ pub struct Arena {
     pub resolution: f32,
 }
 
+impl Arena {
+    /// The world position of the centre of a `(row, column)` cell, matching `spawn_arena_from_map`.
+    pub fn cell_to_world(&self, (row, column): Cell) -> Vec2 {
+        let y_len = (self.arena_map.len() as f32) * self.resolution;
+        let x_len =
+            (self.arena_map.iter().map(|w| w.len()).max().unwrap_or(0) as f32) * self.resolution;
+        Vec2::new(
+            column as f32 * self.resolution - (x_len * 0.5),
+            (y_len * 0.5) - row as f32 * self.resolution,
+        )
+    }
+}
+
+/// Where the player gets placed when they spawn into the current arena.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct PlayerStart(pub Vec2);
+
 pub fn to_map(map: Vec<Vec<u8>>) -> ArenaMap {
     map.iter()
-        .map(|w| w.iter().map(|x| *x == 1).collect())
+        .map(|w| w.iter().map(|x| Tile::from_id(*x)).collect())
         .collect()
 }
 
+/// Solid walls get one collider per merged block so there are few bodies and no seams to snag on,
+/// and one sprite per tile as children of that block. Every other tile gets its own prefab.
 pub fn spawn_arena_from_map(commands: &mut Commands, level: &Arena) {
-    let y_len = (level.arena_map.len() as f32) * level.resolution;
-    let x_len = (level.arena_map.iter().map(|w| w.len()).max().unwrap() as f32) * level.resolution;
-    let mut i = 0.;
-    for row in level.arena_map.iter() {
-        let mut j = 0.;
-        for block in row.iter() {
-            if *block {
-                commands.compose(wall(
-                    j * level.resolution - (x_len * 0.5),
-                    (y_len * 0.5) - i * level.resolution,
-                    level.resolution,
-                    level.resolution,
-                ));
-            };
-            j = j + 1.;
+    let resolution = level.resolution;
+    let solid: Vec<Vec<bool>> = level
+        .arena_map
+        .iter()
+        .map(|w| w.iter().map(|x| *x == Tile::Solid).collect())
+        .collect();
+    for block in merge_walls(&solid) {
+        let top_left = level.cell_to_world((block.row, block.column));
+        let center = top_left
+            + Vec2::new(
+                (block.width - 1) as f32 * 0.5 * resolution,
+                (block.height - 1) as f32 * -0.5 * resolution,
+            );
+        let tree = block.cells().fold(
+            wall_collider(
+                center.x,
+                center.y,
+                block.width as f32 * resolution,
+                block.height as f32 * resolution,
+            ),
+            |tree, cell| {
+                let offset = level.cell_to_world(cell) - center;
+                tree << wall_tile(offset.x, offset.y, resolution)
+            },
+        );
+        commands.compose(tree);
+    }
+
+    for (y, row) in level.arena_map.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let at = level.cell_to_world((y, x));
+            if let Some(tree) = tile_prefab(*tile, at.x, at.y, resolution) {
+                commands.compose(tree);
+            }
         }
-        i = i + 1.;
     }
 }
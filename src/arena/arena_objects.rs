@@ -1,25 +1,175 @@
-use avian2d::prelude::{Collider, CollisionLayers, RigidBody};
-use bevy::{color::Color, core::Name};
+use avian2d::prelude::{
+    AngularDamping, Collider, CollisionLayers, ExternalImpulse, LinearDamping, Mass, RigidBody,
+    Sensor,
+};
+use bevy::{
+    color::Color,
+    core::Name,
+    prelude::{InheritedVisibility, Transform, Visibility},
+};
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::Resource;
 
-use crate::{graphics::rect, twin_stick::physics::GamePhysicsLayer as GPL};
+use super::{
+    props::ExplodeOnDeath,
+    tiles::{Pit, SlowTerrain, Tile},
+};
+use crate::{game::stats::Health, graphics::rect, twin_stick::physics::GamePhysicsLayer as GPL};
 
-pub fn wall(x: f32, y: f32, width: f32, height: f32) -> ComponentTree {
-    rect(x, y, width, height, Color::srgb(0.25, 0.25, 0.75))
+const WALL_COLOR: Color = Color::srgb(0.25, 0.25, 0.75);
+const DESTRUCTIBLE_COLOR: Color = Color::srgb(0.55, 0.4, 0.25);
+const PIT_COLOR: Color = Color::srgb(0.05, 0.05, 0.05);
+const SLOW_COLOR: Color = Color::srgb(0.4, 0.55, 0.3);
+const HALF_WALL_COLOR: Color = Color::srgb(0.5, 0.6, 0.8);
+const CRATE_COLOR: Color = Color::srgb(0.7, 0.5, 0.3);
+const BARREL_COLOR: Color = Color::srgb(0.8, 0.2, 0.1);
+const COVER_COLOR: Color = Color::srgb(0.45, 0.45, 0.5);
+const EXIT_COLOR: Color = Color::srgba(0.9, 0.8, 0.2, 0.3);
+
+fn wall_body(width: f32, height: f32) -> ComponentTree {
+    (
+        RigidBody::Static,
+        Collider::rectangle(width, height),
+        Name::new("Wall"),
+        CollisionLayers::new(
+            GPL::MapSolid,
+            [
+                GPL::Player,
+                GPL::Enemy,
+                GPL::MapDynamic,
+                GPL::MapSolid,
+                GPL::Bullet,
+                GPL::EnemyBullet,
+            ],
+        ),
+    )
+        .store()
+}
+
+/// A wall collider with no sprite of its own, for merged blocks of wall tiles.
+pub fn wall_collider(x: f32, y: f32, width: f32, height: f32) -> ComponentTree {
+    (
+        Transform::from_xyz(x, y, 0.),
+        Visibility::Visible,
+        InheritedVisibility::default(),
+    )
+        .store()
+        + wall_body(width, height)
+}
+
+/// The sprite for a single wall tile, relative to its parent collider.
+pub fn wall_tile(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, WALL_COLOR) + Name::new("Wall Tile").store()
+}
+
+pub fn destructible_wall(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, DESTRUCTIBLE_COLOR)
+        + wall_body(size, size)
+        + (Name::new("Destructible Wall"), Resource::<Health>::new(5.)).store()
+}
+
+/// Only actors can stand on these, so they sit on the trigger layer where bullets never look.
+fn floor_trigger(size: f32) -> ComponentTree {
+    (
+        RigidBody::Static,
+        Sensor,
+        Collider::rectangle(size, size),
+        CollisionLayers::new(GPL::Trigger, [GPL::Player, GPL::Enemy]),
+    )
+        .store()
+}
+
+pub fn pit(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, PIT_COLOR) + floor_trigger(size) + (Pit, Name::new("Pit")).store()
+}
+
+pub fn slow_terrain(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, SLOW_COLOR)
+        + floor_trigger(size)
+        + (SlowTerrain(0.5), Name::new("Slow Terrain")).store()
+}
+
+pub fn half_wall(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, HALF_WALL_COLOR)
         + (
             RigidBody::Static,
-            Collider::rectangle(width, height),
-            Name::new("Wall"),
-            CollisionLayers::new(
+            Collider::rectangle(size, size),
+            Name::new("Half Wall"),
+            CollisionLayers::new(GPL::Ethereal, [GPL::Player, GPL::Enemy, GPL::MapDynamic]),
+        )
+            .store()
+}
+
+/// The prefab for every tile that isn't plain floor or merged into a solid block.
+pub fn tile_prefab(tile: Tile, x: f32, y: f32, size: f32) -> Option<ComponentTree> {
+    match tile {
+        Tile::Floor | Tile::Solid => None,
+        Tile::Destructible => Some(destructible_wall(x, y, size)),
+        Tile::Pit => Some(pit(x, y, size)),
+        Tile::Slow => Some(slow_terrain(x, y, size)),
+        Tile::HalfWall => Some(half_wall(x, y, size)),
+    }
+}
+
+/// A loose object on `MapDynamic`: it gets shoved around by actors and knockback, and breaks
+/// like an actor dies once its health runs out.
+fn prop(size: f32, mass: f32, health: f32) -> ComponentTree {
+    (
+        RigidBody::Dynamic,
+        Collider::rectangle(size, size),
+        Mass(mass),
+        LinearDamping(8.),
+        AngularDamping(8.),
+        ExternalImpulse::default(),
+        Resource::<Health>::new(health),
+        CollisionLayers::new(
+            GPL::MapDynamic,
+            [
+                GPL::Player,
+                GPL::Enemy,
                 GPL::MapSolid,
-                [
-                    GPL::Player,
-                    GPL::Enemy,
-                    GPL::MapDynamic,
-                    GPL::MapSolid,
-                    GPL::Bullet,
-                ],
-            ),
+                GPL::MapDynamic,
+                GPL::Ethereal,
+                GPL::Bullet,
+                GPL::EnemyBullet,
+            ],
+        ),
+    )
+        .store()
+}
+
+pub fn pushable_crate(x: f32, y: f32) -> ComponentTree {
+    rect(x, y, 30., 30., CRATE_COLOR) + prop(30., 40., 4.) + Name::new("Crate").store()
+}
+
+pub fn explosive_barrel(x: f32, y: f32) -> ComponentTree {
+    rect(x, y, 24., 24., BARREL_COLOR)
+        + prop(24., 30., 1.)
+        + (
+            Name::new("Explosive Barrel"),
+            ExplodeOnDeath {
+                radius: 90.,
+                damage: 3.,
+            },
+        )
+            .store()
+}
+
+/// Heavy enough that nothing moves it much, so it mostly soaks up shots until it breaks.
+pub fn cover(x: f32, y: f32) -> ComponentTree {
+    rect(x, y, 48., 48., COVER_COLOR) + prop(48., 2000., 12.) + Name::new("Cover").store()
+}
+
+/// Starts out faded and locked; the director lights it up once the arena is cleared.
+pub fn arena_exit(x: f32, y: f32, size: f32) -> ComponentTree {
+    rect(x, y, size, size, EXIT_COLOR)
+        + (
+            RigidBody::Static,
+            Sensor,
+            Collider::rectangle(size, size),
+            CollisionLayers::new(GPL::Trigger, [GPL::Player]),
+            ArenaExit { locked: true },
+            Name::new("Exit"),
         )
             .store()
 }
@@ -0,0 +1,359 @@
+use bevy::{ecs::system::Resource, reflect::Reflect};
+use bevy_turborand::{DelegatedRng, RngComponent};
+use std::collections::VecDeque;
+
+use super::{arena::ArenaMap, props::PropKind, tiles::Tile};
+
+pub type Cell = (usize, usize);
+
+/// The plain wall/floor grid the cellular automaton works on before it becomes tiles.
+type WallGrid = Vec<Vec<bool>>;
+
+/// Knobs for the cellular-automata arena generator. Lives as a resource so it can be tuned from
+/// the inspector or the arena preview window.
+#[derive(Resource, Reflect, Clone, Debug, PartialEq)]
+pub struct ArenaGenConfig {
+    /// Sizes below `MIN_ARENA_SIZE` are raised to it.
+    pub width: usize,
+    pub height: usize,
+    /// Chance for each interior cell to start as a wall.
+    pub fill_chance: f32,
+    pub smoothing_steps: usize,
+    /// A floor cell with at least this many wall neighbours becomes a wall.
+    pub birth_limit: usize,
+    /// A wall cell with fewer than this many wall neighbours becomes floor.
+    pub death_limit: usize,
+    /// The smallest share of the map the connected floor may cover before we reroll.
+    pub min_open_ratio: f32,
+    pub spawner_count: usize,
+    /// How many steps away from the player start a spawner has to be.
+    pub min_spawner_distance: usize,
+    pub max_difficulty: u32,
+    pub prop_count: usize,
+}
+
+impl Default for ArenaGenConfig {
+    fn default() -> Self {
+        Self {
+            width: 40,
+            height: 30,
+            fill_chance: 0.45,
+            smoothing_steps: 4,
+            birth_limit: 5,
+            death_limit: 4,
+            min_open_ratio: 0.35,
+            spawner_count: 4,
+            min_spawner_distance: 8,
+            max_difficulty: 3,
+            prop_count: 8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpawnPoint {
+    pub cell: Cell,
+    pub difficulty: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedArena {
+    pub seed: u64,
+    pub map: ArenaMap,
+    pub player_start: Cell,
+    /// The reachable cell furthest from the start.
+    pub exit: Cell,
+    pub spawners: Vec<SpawnPoint>,
+    pub props: Vec<(Cell, PropKind)>,
+}
+
+const MAX_ATTEMPTS: u64 = 16;
+
+/// The smallest arena that still has a floor cell inside its border walls.
+pub const MIN_ARENA_SIZE: usize = 3;
+
+/// Build an arena from `seed`. The same seed and config always give the same arena.
+pub fn generate_arena(seed: u64, config: &ArenaGenConfig) -> GeneratedArena {
+    let config = &ArenaGenConfig {
+        width: config.width.max(MIN_ARENA_SIZE),
+        height: config.height.max(MIN_ARENA_SIZE),
+        ..config.clone()
+    };
+    let mut best: Option<(ArenaMap, Vec<Cell>)> = None;
+    for attempt in 0..MAX_ATTEMPTS {
+        let mut rng = RngComponent::with_seed(seed.wrapping_add(attempt));
+        let mut grid = random_fill(&mut rng, config);
+        for _ in 0..config.smoothing_steps {
+            grid = smooth(&grid, config);
+        }
+        let map: ArenaMap = grid
+            .iter()
+            .map(|w| {
+                w.iter()
+                    .map(|x| if *x { Tile::Solid } else { Tile::Floor })
+                    .collect()
+            })
+            .collect();
+        let region = largest_region(&map);
+        let open_ratio = region.len() as f32 / (config.width * config.height) as f32;
+        if best.as_ref().is_none_or(|(_, w)| region.len() > w.len()) {
+            best = Some((map, region));
+        }
+        if open_ratio >= config.min_open_ratio {
+            break;
+        }
+    }
+    let (mut map, region) = best.unwrap();
+
+    // Anything the player can't walk to becomes wall, so the arena is always one connected space.
+    for row in map.iter_mut() {
+        row.fill(Tile::Solid);
+    }
+    for (y, x) in region.iter() {
+        map[*y][*x] = Tile::Floor;
+    }
+
+    let center = (config.height / 2, config.width / 2);
+    let player_start = *region
+        .iter()
+        .min_by_key(|(y, x)| y.abs_diff(center.0).pow(2) + x.abs_diff(center.1).pow(2))
+        .unwrap_or(&center);
+    let exit = furthest_cell(&map, player_start);
+    let spawners = place_spawners(&map, player_start, config);
+    let props = scatter_props(
+        &mut RngComponent::with_seed(seed.rotate_left(32)),
+        &map,
+        player_start,
+        &spawners,
+        exit,
+        config,
+    );
+
+    GeneratedArena {
+        seed,
+        map,
+        player_start,
+        exit,
+        spawners,
+        props,
+    }
+}
+
+fn random_fill(rng: &mut RngComponent, config: &ArenaGenConfig) -> WallGrid {
+    (0..config.height)
+        .map(|y| {
+            (0..config.width)
+                .map(|x| {
+                    let border =
+                        y == 0 || x == 0 || y == config.height - 1 || x == config.width - 1;
+                    border || rng.f32() < config.fill_chance
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn wall_neighbours(map: &WallGrid, y: usize, x: usize) -> usize {
+    let mut count = 0;
+    for dy in -1..=1_isize {
+        for dx in -1..=1_isize {
+            if dy == 0 && dx == 0 {
+                continue;
+            }
+            let (ny, nx) = (y as isize + dy, x as isize + dx);
+            let wall = ny < 0
+                || nx < 0
+                || map
+                    .get(ny as usize)
+                    .and_then(|w| w.get(nx as usize))
+                    .copied()
+                    .unwrap_or(true);
+            if wall {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn smooth(map: &WallGrid, config: &ArenaGenConfig) -> WallGrid {
+    map.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, wall)| {
+                    let border =
+                        y == 0 || x == 0 || y == config.height - 1 || x == config.width - 1;
+                    let neighbours = wall_neighbours(map, y, x);
+                    border
+                        || if *wall {
+                            neighbours >= config.death_limit
+                        } else {
+                            neighbours >= config.birth_limit
+                        }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn floor_neighbours(map: &ArenaMap, (y, x): Cell) -> impl Iterator<Item = Cell> + '_ {
+    [(0, 1), (2, 1), (1, 0), (1, 2)]
+        .into_iter()
+        .filter_map(move |(dy, dx)| Some(((y + dy).checked_sub(1)?, (x + dx).checked_sub(1)?)))
+        .filter(|(y, x)| {
+            map.get(*y)
+                .and_then(|w| w.get(*x))
+                .is_some_and(|w| w.is_walkable())
+        })
+}
+
+/// Breadth-first distances from `start` to every reachable floor cell.
+pub fn distances(map: &ArenaMap, start: Cell) -> Vec<(Cell, usize)> {
+    let mut seen = vec![vec![false; map.first().map(|w| w.len()).unwrap_or(0)]; map.len()];
+    let mut found = Vec::new();
+    let mut queue = VecDeque::from([(start, 0)]);
+    seen[start.0][start.1] = true;
+    while let Some((cell, distance)) = queue.pop_front() {
+        found.push((cell, distance));
+        for next in floor_neighbours(map, cell) {
+            if !seen[next.0][next.1] {
+                seen[next.0][next.1] = true;
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    found
+}
+
+pub fn furthest_cell(map: &ArenaMap, start: Cell) -> Cell {
+    distances(map, start)
+        .into_iter()
+        .max_by_key(|w| w.1)
+        .map(|w| w.0)
+        .unwrap_or(start)
+}
+
+fn largest_region(map: &ArenaMap) -> Vec<Cell> {
+    let mut claimed = vec![vec![false; map.first().map(|w| w.len()).unwrap_or(0)]; map.len()];
+    let mut best = Vec::new();
+    for (y, row) in map.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            if !tile.is_walkable() || claimed[y][x] {
+                continue;
+            }
+            let region: Vec<Cell> = distances(map, (y, x)).into_iter().map(|w| w.0).collect();
+            for (ry, rx) in region.iter() {
+                claimed[*ry][*rx] = true;
+            }
+            if region.len() > best.len() {
+                best = region;
+            }
+        }
+    }
+    best
+}
+
+/// Spread spawners over the cells far enough from the start, harder ones further away.
+fn place_spawners(map: &ArenaMap, start: Cell, config: &ArenaGenConfig) -> Vec<SpawnPoint> {
+    let reachable = distances(map, start);
+    let max_distance = reachable.iter().map(|w| w.1).max().unwrap_or(0).max(1);
+    let mut candidates: Vec<(Cell, usize)> = reachable
+        .into_iter()
+        .filter(|(_, distance)| *distance >= config.min_spawner_distance)
+        .collect();
+
+    let mut spawners: Vec<SpawnPoint> = Vec::new();
+    while spawners.len() < config.spawner_count && !candidates.is_empty() {
+        // Farthest-point sampling: take the candidate furthest from every spawner placed so far.
+        let (index, _) = candidates
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, ((y, x), distance))| {
+                spawners
+                    .iter()
+                    .map(|w| w.cell.0.abs_diff(*y) + w.cell.1.abs_diff(*x))
+                    .min()
+                    .unwrap_or(*distance)
+            })
+            .unwrap();
+        let (cell, distance) = candidates.swap_remove(index);
+        spawners.push(SpawnPoint {
+            cell,
+            difficulty: 1
+                + (distance * config.max_difficulty.saturating_sub(1) as usize / max_distance)
+                    as u32,
+        });
+    }
+    spawners
+}
+
+/// Drop props on random open cells, keeping them off the start and the spawners.
+fn scatter_props(
+    rng: &mut RngComponent,
+    map: &ArenaMap,
+    start: Cell,
+    spawners: &[SpawnPoint],
+    exit: Cell,
+    config: &ArenaGenConfig,
+) -> Vec<(Cell, PropKind)> {
+    let mut candidates: Vec<Cell> = distances(map, start)
+        .into_iter()
+        .filter(|(cell, distance)| {
+            *distance >= 3 && *cell != exit && spawners.iter().all(|w| w.cell != *cell)
+        })
+        .map(|w| w.0)
+        .collect();
+    let mut props = Vec::new();
+    while props.len() < config.prop_count && !candidates.is_empty() {
+        let cell = candidates.swap_remove(rng.usize(0..candidates.len()));
+        let kind = PropKind::ALL[rng.usize(0..PropKind::ALL.len())];
+        props.push((cell, kind));
+    }
+    props
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_arena() {
+        let config = ArenaGenConfig::default();
+        assert_eq!(generate_arena(7, &config), generate_arena(7, &config));
+        assert_ne!(
+            generate_arena(7, &config).map,
+            generate_arena(8, &config).map
+        );
+    }
+
+    #[test]
+    fn every_floor_cell_is_reachable_from_the_start() {
+        let config = ArenaGenConfig::default();
+        for seed in 0..20 {
+            let arena = generate_arena(seed, &config);
+            let reachable = distances(&arena.map, arena.player_start);
+            let floor = arena
+                .map
+                .iter()
+                .flatten()
+                .filter(|w| w.is_walkable())
+                .count();
+            assert!(arena.map[arena.player_start.0][arena.player_start.1].is_walkable());
+            assert_eq!(reachable.len(), floor, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn empty_config_is_raised_to_the_minimum_size() {
+        let config = ArenaGenConfig {
+            width: 0,
+            height: 0,
+            ..Default::default()
+        };
+        let arena = generate_arena(1, &config);
+        assert_eq!(arena.map.len(), MIN_ARENA_SIZE);
+        assert!(arena.map.iter().all(|w| w.len() == MIN_ARENA_SIZE));
+    }
+}
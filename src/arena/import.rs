@@ -0,0 +1,486 @@
+use bevy::{
+    asset::{
+        io::Reader, Asset, AssetEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext,
+        LoadedFolder,
+    },
+    ecs::{
+        event::EventReader,
+        system::{Commands, Res, ResMut, Resource},
+    },
+    reflect::TypePath,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{collections::HashMap, error::Error, path::Path};
+
+use super::{arena::ArenaMap, generator::Cell, tiles::Tile};
+
+/// Where the level files live, relative to the asset folder.
+pub const LEVEL_DIR: &str = "levels";
+/// Grid and tile layers with this name (case-insensitive) hold the arena tiles. IntGrid values
+/// and Tiled tile ids are read with `Tile::try_from_id`, so the first tile of a tileset is a wall.
+pub const WALL_LAYER: &str = "walls";
+
+/// A hand-made arena read from an LDtk project or a Tiled map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedLevel {
+    pub identifier: String,
+    pub map: ArenaMap,
+    pub entities: Vec<LevelEntity>,
+}
+
+/// An entity placed by a level designer. `identifier` picks the prefab, `fields` holds the numeric
+/// custom fields, which are mapped onto stats.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelEntity {
+    pub identifier: String,
+    pub cell: Cell,
+    pub fields: HashMap<String, f32>,
+}
+
+#[derive(Deserialize)]
+struct LdtkProject {
+    levels: Vec<LdtkLevel>,
+}
+
+#[derive(Deserialize)]
+struct LdtkLevel {
+    identifier: String,
+    #[serde(rename = "layerInstances")]
+    layer_instances: Option<Vec<LdtkLayer>>,
+}
+
+#[derive(Deserialize)]
+struct LdtkLayer {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    kind: String,
+    #[serde(rename = "__cWid")]
+    width: usize,
+    #[serde(rename = "__cHei")]
+    height: usize,
+    #[serde(rename = "intGridCsv", default)]
+    int_grid: Vec<i64>,
+    #[serde(rename = "entityInstances", default)]
+    entities: Vec<LdtkEntity>,
+}
+
+#[derive(Deserialize)]
+struct LdtkEntity {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__grid")]
+    grid: [usize; 2],
+    #[serde(rename = "fieldInstances", default)]
+    fields: Vec<LdtkField>,
+}
+
+#[derive(Deserialize)]
+struct LdtkField {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__value")]
+    value: Value,
+}
+
+#[derive(Deserialize)]
+struct TiledMap {
+    width: usize,
+    height: usize,
+    #[serde(rename = "tilewidth")]
+    tile_width: f32,
+    #[serde(rename = "tileheight")]
+    tile_height: f32,
+    layers: Vec<TiledLayer>,
+    #[serde(default)]
+    tilesets: Vec<TiledTileset>,
+}
+
+#[derive(Deserialize)]
+struct TiledTileset {
+    firstgid: u32,
+}
+
+#[derive(Deserialize)]
+struct TiledLayer {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    data: Vec<i64>,
+    #[serde(default)]
+    objects: Vec<TiledObject>,
+}
+
+#[derive(Deserialize)]
+struct TiledObject {
+    name: String,
+    /// Called `class` since Tiled 1.9 and `type` before that.
+    #[serde(alias = "class", rename = "type", default)]
+    class: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    properties: Vec<TiledProperty>,
+}
+
+#[derive(Deserialize)]
+struct TiledProperty {
+    name: String,
+    value: Value,
+}
+
+type LevelResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Tiled keeps a tile's flip and rotation flags in the top bits of its GID.
+const TILED_FLAG_BITS: u32 = 0xF000_0000;
+
+fn numeric(value: &Value) -> Option<f32> {
+    match value {
+        Value::Number(number) => number.as_f64().map(|w| w as f32),
+        Value::Bool(flag) => Some(if *flag { 1. } else { 0. }),
+        _ => None,
+    }
+}
+
+fn tile_from_id(id: i64) -> LevelResult<Tile> {
+    u8::try_from(id)
+        .ok()
+        .and_then(Tile::try_from_id)
+        .ok_or_else(|| format!("Unknown tile id {}", id).into())
+}
+
+fn grid_to_map(width: usize, height: usize, cells: &[i64]) -> LevelResult<ArenaMap> {
+    if width == 0 || height == 0 {
+        return Err(format!("Wall layer is {}x{}, which has no cells", width, height).into());
+    }
+    if cells.len() != width * height {
+        return Err(format!(
+            "Wall layer has {} cells but should have {}x{}",
+            cells.len(),
+            width,
+            height
+        )
+        .into());
+    }
+    cells
+        .chunks(width)
+        .map(|row| row.iter().map(|w| tile_from_id(*w)).collect())
+        .collect()
+}
+
+/// The id of a Tiled tile within its own tileset, counting from 1 so that empty cells stay 0.
+fn tiled_tile_id(gid: u32, tilesets: &[TiledTileset]) -> LevelResult<i64> {
+    let gid = gid & !TILED_FLAG_BITS;
+    if gid == 0 {
+        return Ok(0);
+    }
+    let firstgid = tilesets
+        .iter()
+        .map(|w| w.firstgid)
+        .filter(|w| *w <= gid)
+        .max()
+        .ok_or_else(|| format!("Tile {} is not in any tileset", gid))?;
+    Ok(i64::from(gid - firstgid) + 1)
+}
+
+pub fn parse_ldtk(bytes: &[u8]) -> LevelResult<Vec<ImportedLevel>> {
+    let project: LdtkProject = serde_json::from_slice(bytes)?;
+    project
+        .levels
+        .into_iter()
+        .map(|level| {
+            let layers = level.layer_instances.ok_or_else(|| {
+                format!(
+                    "Level {} is saved externally, which is not supported",
+                    level.identifier
+                )
+            })?;
+            let walls = layers
+                .iter()
+                .find(|w| w.kind == "IntGrid" && w.identifier.eq_ignore_ascii_case(WALL_LAYER))
+                .ok_or_else(|| format!("Level {} has no Walls layer", level.identifier))?;
+            let entities = layers
+                .iter()
+                .filter(|w| w.kind == "Entities")
+                .flat_map(|w| w.entities.iter())
+                .map(|entity| LevelEntity {
+                    identifier: entity.identifier.clone(),
+                    cell: (entity.grid[1], entity.grid[0]),
+                    fields: entity
+                        .fields
+                        .iter()
+                        .filter_map(|w| Some((w.identifier.clone(), numeric(&w.value)?)))
+                        .collect(),
+                })
+                .collect();
+            Ok(ImportedLevel {
+                identifier: level.identifier,
+                map: grid_to_map(walls.width, walls.height, &walls.int_grid)?,
+                entities,
+            })
+        })
+        .collect()
+}
+
+/// Tiled maps hold a single level, so it's named after the file it came from.
+pub fn parse_tiled(bytes: &[u8], identifier: &str) -> LevelResult<ImportedLevel> {
+    let map: TiledMap = serde_json::from_slice(bytes)?;
+    let walls = map
+        .layers
+        .iter()
+        .find(|w| w.kind == "tilelayer" && w.name.eq_ignore_ascii_case(WALL_LAYER))
+        .ok_or("Map has no Walls layer")?;
+    let entities = map
+        .layers
+        .iter()
+        .filter(|w| w.kind == "objectgroup")
+        .flat_map(|w| w.objects.iter())
+        .map(|object| LevelEntity {
+            identifier: if object.class.is_empty() {
+                object.name.clone()
+            } else {
+                object.class.clone()
+            },
+            cell: (
+                (object.y / map.tile_height) as usize,
+                (object.x / map.tile_width) as usize,
+            ),
+            fields: object
+                .properties
+                .iter()
+                .filter_map(|w| Some((w.name.clone(), numeric(&w.value)?)))
+                .collect(),
+        })
+        .collect();
+    let ids = walls
+        .data
+        .iter()
+        .map(|w| tiled_tile_id(*w, &map.tilesets))
+        .collect::<LevelResult<Vec<_>>>()?;
+    Ok(ImportedLevel {
+        identifier: identifier.to_string(),
+        map: grid_to_map(map.width, map.height, &ids)?,
+        entities,
+    })
+}
+
+/// Every level in an `.ldtk` project or a `.tmj` Tiled map.
+pub fn parse_level_file(path: &Path, bytes: &[u8]) -> LevelResult<Vec<ImportedLevel>> {
+    match path.extension().and_then(|w| w.to_str()) {
+        Some("ldtk") => parse_ldtk(bytes),
+        Some("tmj") => Ok(vec![parse_tiled(
+            bytes,
+            &path
+                .file_stem()
+                .map(|w| w.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        )?]),
+        _ => Err(format!("{} is not an LDtk or Tiled file", path.display()).into()),
+    }
+}
+
+/// The levels read from one file in `LEVEL_DIR`.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct LevelFile {
+    pub levels: Vec<ImportedLevel>,
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = LevelFile;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(LevelFile {
+            levels: parse_level_file(load_context.path(), &bytes)?,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk", "tmj"]
+    }
+}
+
+/// Keeps the files in `LEVEL_DIR` loaded.
+#[derive(Resource, Clone, Debug)]
+pub struct LevelFolder(pub Handle<LoadedFolder>);
+
+/// Every hand-made arena found in `LEVEL_DIR`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct LevelLibrary {
+    pub levels: Vec<ImportedLevel>,
+}
+
+impl LevelLibrary {
+    /// The levels whose identifier starts with `prefix`, ignoring case.
+    pub fn tagged<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a ImportedLevel> {
+        self.levels.iter().filter(move |w| {
+            w.identifier
+                .get(..prefix.len())
+                .is_some_and(|w| w.eq_ignore_ascii_case(prefix))
+        })
+    }
+}
+
+pub fn load_level_folder(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelFolder(asset_server.load_folder(LEVEL_DIR)));
+}
+
+/// Rebuild the library whenever a level file finishes loading or is edited, in file name order
+/// so a run's seed always picks the same level.
+pub fn update_level_library(
+    mut events: EventReader<AssetEvent<LevelFile>>,
+    files: Res<Assets<LevelFile>>,
+    asset_server: Res<AssetServer>,
+    mut library: ResMut<LevelLibrary>,
+) {
+    if events.read().count() == 0 {
+        return;
+    }
+    let mut loaded: Vec<(String, &LevelFile)> = files
+        .iter()
+        .map(|(id, file)| {
+            (
+                asset_server
+                    .get_path(id)
+                    .map(|w| w.to_string())
+                    .unwrap_or_default(),
+                file,
+            )
+        })
+        .collect();
+    loaded.sort_by(|a, b| a.0.cmp(&b.0));
+    library.levels = loaded
+        .into_iter()
+        .flat_map(|w| w.1.levels.iter().cloned())
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LDTK: &[u8] = include_bytes!("fixtures/arena_test.ldtk");
+    const TILED: &[u8] = include_bytes!("fixtures/elite_test.tmj");
+
+    fn border_map() -> ArenaMap {
+        vec![
+            vec![Tile::Solid; 4],
+            vec![Tile::Solid, Tile::Floor, Tile::Slow, Tile::Solid],
+            vec![Tile::Solid; 4],
+        ]
+    }
+
+    #[test]
+    fn reads_ldtk_walls_and_entities() {
+        let levels = parse_ldtk(LDTK).unwrap();
+        assert_eq!(levels.len(), 1);
+        let level = &levels[0];
+        assert_eq!(level.identifier, "Arena_Test");
+        assert_eq!(level.map, border_map());
+        assert_eq!(
+            level.entities,
+            vec![
+                LevelEntity {
+                    identifier: "PlayerStart".to_string(),
+                    cell: (1, 1),
+                    fields: HashMap::new(),
+                },
+                LevelEntity {
+                    identifier: "Stumbler".to_string(),
+                    cell: (1, 2),
+                    // Fields that aren't numbers are dropped.
+                    fields: HashMap::from([("Health".to_string(), 12.)]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_tiled_walls_and_objects() {
+        let level = parse_tiled(TILED, "Elite_Test").unwrap();
+        assert_eq!(level.identifier, "Elite_Test");
+        assert_eq!(level.map, border_map());
+        assert_eq!(
+            level.entities,
+            vec![
+                LevelEntity {
+                    identifier: "Player".to_string(),
+                    cell: (1, 1),
+                    fields: HashMap::new(),
+                },
+                LevelEntity {
+                    identifier: "EnemySpawn".to_string(),
+                    cell: (1, 2),
+                    fields: HashMap::from([("Difficulty".to_string(), 2.)]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn picks_the_parser_by_extension() {
+        let level = parse_level_file(Path::new("levels/elite_test.tmj"), TILED).unwrap();
+        assert_eq!(level[0].identifier, "elite_test");
+        assert_eq!(
+            parse_level_file(Path::new("levels/arena_test.ldtk"), LDTK)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(parse_level_file(Path::new("levels/notes.txt"), LDTK).is_err());
+    }
+
+    #[test]
+    fn rejects_a_wall_layer_of_the_wrong_size() {
+        assert!(grid_to_map(4, 3, &[0; 11]).is_err());
+        assert!(grid_to_map(0, 3, &[]).is_err());
+        assert!(grid_to_map(4, 0, &[]).is_err());
+        assert!(parse_tiled(
+            br#"{"width":2,"height":2,"tilewidth":16,"tileheight":16,"layers":[]}"#,
+            "Empty"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reads_tiled_gids_relative_to_their_tileset() {
+        let tilesets = [TiledTileset { firstgid: 1 }, TiledTileset { firstgid: 9 }];
+        assert_eq!(tiled_tile_id(0, &tilesets).unwrap(), 0);
+        assert_eq!(tiled_tile_id(1, &tilesets).unwrap(), 1);
+        assert_eq!(tiled_tile_id(9, &tilesets).unwrap(), 1);
+        assert_eq!(tiled_tile_id(10, &tilesets).unwrap(), 2);
+        // Flipped horizontally and vertically.
+        assert_eq!(tiled_tile_id(9 | 0xC000_0000, &tilesets).unwrap(), 1);
+        assert!(tiled_tile_id(5, &[TiledTileset { firstgid: 9 }]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_tile_ids() {
+        assert!(grid_to_map(1, 1, &[6]).is_err());
+        assert!(grid_to_map(1, 1, &[-1]).is_err());
+    }
+
+    #[test]
+    fn shipped_levels_load() {
+        let level = parse_tiled(
+            include_bytes!("../../assets/levels/elite_crossroads.tmj"),
+            "elite_crossroads",
+        )
+        .unwrap();
+        assert_eq!(level.map.len(), 12);
+        assert!(level.entities.iter().any(|w| w.identifier == "Player"));
+    }
+}
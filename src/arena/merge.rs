@@ -0,0 +1,118 @@
+//! Greedy merging of a wall grid into as few rectangles as it can manage. Only depends on std so
+//! the library target can hand it to the benchmark.
+
+/// A block of wall cells, measured in cells from the top left of the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TileRect {
+    pub row: usize,
+    pub column: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl TileRect {
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.row..self.row + self.height)
+            .flat_map(move |y| (self.column..self.column + self.width).map(move |x| (y, x)))
+    }
+}
+
+/// Cover every `true` cell with exactly one rectangle. Each rectangle grows right as far as it can,
+/// then down for as long as the whole run below is still unclaimed wall.
+pub fn merge_walls(map: &[Vec<bool>]) -> Vec<TileRect> {
+    let width = map.iter().map(|w| w.len()).max().unwrap_or(0);
+    let wall = |y: usize, x: usize| map[y].get(x).copied().unwrap_or(false);
+    let mut claimed = vec![vec![false; width]; map.len()];
+    let mut rects = Vec::new();
+
+    for y in 0..map.len() {
+        for x in 0..width {
+            if !wall(y, x) || claimed[y][x] {
+                continue;
+            }
+            let mut run = 1;
+            while x + run < width && wall(y, x + run) && !claimed[y][x + run] {
+                run += 1;
+            }
+            let mut height = 1;
+            while y + height < map.len()
+                && (x..x + run)
+                    .all(|column| wall(y + height, column) && !claimed[y + height][column])
+            {
+                height += 1;
+            }
+            let rect = TileRect {
+                row: y,
+                column: x,
+                width: run,
+                height,
+            };
+            for (cy, cx) in rect.cells() {
+                claimed[cy][cx] = true;
+            }
+            rects.push(rect);
+        }
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every wall cell is covered by exactly one rectangle and no floor cell by any.
+    fn assert_exact_cover(map: &[Vec<bool>]) {
+        let mut covered = vec![vec![0; map.first().map(|w| w.len()).unwrap_or(0)]; map.len()];
+        for rect in merge_walls(map) {
+            for (y, x) in rect.cells() {
+                covered[y][x] += 1;
+            }
+        }
+        for (y, row) in map.iter().enumerate() {
+            for (x, wall) in row.iter().enumerate() {
+                assert_eq!(covered[y][x], *wall as u32, "cell ({y}, {x})");
+            }
+        }
+    }
+
+    #[test]
+    fn solid_map_is_one_rect() {
+        let map = vec![vec![true; 5]; 4];
+        assert_eq!(
+            merge_walls(&map),
+            vec![TileRect {
+                row: 0,
+                column: 0,
+                width: 5,
+                height: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn rects_cover_walls_exactly_once() {
+        assert_exact_cover(&[]);
+        assert_exact_cover(&vec![vec![false; 6]; 6]);
+        assert_exact_cover(
+            &(0..9)
+                .map(|y| (0..7).map(|x| (x + y) % 2 == 0).collect())
+                .collect::<Vec<_>>(),
+        );
+        let mut state: u32 = 0x2545_f491;
+        for _ in 0..20 {
+            let map: Vec<Vec<bool>> = (0..12)
+                .map(|_| {
+                    (0..15)
+                        .map(|_| {
+                            state ^= state << 13;
+                            state ^= state >> 17;
+                            state ^= state << 5;
+                            state % 3 != 0
+                        })
+                        .collect()
+                })
+                .collect();
+            assert_exact_cover(&map);
+        }
+    }
+}
@@ -1,4 +1,24 @@
-pub use arena::{spawn_arena_from_map, to_map, Arena};
+pub use arena::{spawn_arena_from_map, to_map, Arena, PlayerStart};
+pub use generator::{generate_arena, ArenaGenConfig, GeneratedArena, SpawnPoint};
+pub use lock2::arena::merge;
+pub use tiles::Tile;
+
+use bevy::app::{App, Plugin};
+use props::props_plugin;
+use tiles::tiles_plugin;
 
 pub mod arena;
 pub mod arena_objects;
+pub mod generator;
+pub mod import;
+pub mod props;
+pub mod tiles;
+
+pub struct ArenaPlugin;
+
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        tiles_plugin(app);
+        props_plugin(app);
+    }
+}
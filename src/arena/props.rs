@@ -0,0 +1,73 @@
+use bevy::{
+    app::{App, Update},
+    math::Vec3Swizzles,
+    prelude::{Changed, Component, Entity, EventWriter, Query, Transform},
+    reflect::Reflect,
+};
+use bevy_composable::tree::ComponentTree;
+use bevy_stats::Resource;
+
+use super::arena_objects::{cover, explosive_barrel, pushable_crate};
+use crate::{game::stats::Health, twin_stick::events::ExplosionEvent};
+
+/// The loose objects that sit on `MapDynamic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum PropKind {
+    Crate,
+    Barrel,
+    Cover,
+}
+
+impl PropKind {
+    pub const ALL: [PropKind; 3] = [PropKind::Crate, PropKind::Barrel, PropKind::Cover];
+
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        match identifier {
+            "Crate" => Some(PropKind::Crate),
+            "Barrel" => Some(PropKind::Barrel),
+            "Cover" => Some(PropKind::Cover),
+            _ => None,
+        }
+    }
+
+    pub fn prefab(&self, x: f32, y: f32) -> ComponentTree {
+        match self {
+            PropKind::Crate => pushable_crate(x, y),
+            PropKind::Barrel => explosive_barrel(x, y),
+            PropKind::Cover => cover(x, y),
+        }
+    }
+}
+
+/// Blows up when its health runs out.
+#[derive(Component, Clone, Copy, PartialEq, Reflect, Debug)]
+pub struct ExplodeOnDeath {
+    pub radius: f32,
+    pub damage: f32,
+}
+
+pub(super) fn props_plugin(app: &mut App) {
+    app.register_type::<PropKind>()
+        .register_type::<ExplodeOnDeath>();
+    app.add_systems(Update, explode_on_death);
+}
+
+pub fn explode_on_death(
+    props: Query<
+        (Entity, &ExplodeOnDeath, &Transform, &Resource<Health>),
+        Changed<Resource<Health>>,
+    >,
+    mut explosions: EventWriter<ExplosionEvent>,
+) {
+    for (entity, explosive, transform, health) in props.iter() {
+        if health.current_value() <= 0. {
+            explosions.send(ExplosionEvent {
+                source: entity,
+                center: transform.translation.xy(),
+                radius: explosive.radius,
+                damage: explosive.damage,
+                spare: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use avian2d::prelude::{CollisionEnded, CollisionStarted};
+use bevy::{
+    app::{App, Update},
+    prelude::{
+        Commands, Component, Entity, EventReader, EventWriter, IntoSystemConfigs, Query, With,
+    },
+    reflect::Reflect,
+    utils::HashMap,
+};
+use bevy_stats::{
+    statmod::{ModType, StatModifier, StatValueChange},
+    DeleteStatMod, Resource, Stat,
+};
+
+use crate::{
+    game::stats::{Health, MoveSpeed},
+    twin_stick::{actors::Actor, events::DamageEvent},
+};
+
+/// What a single arena cell holds. The ids are what `to_map` and the level importers read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum Tile {
+    #[default]
+    Floor,
+    Solid,
+    /// A wall with `Resource<Health>` that bullets can break.
+    Destructible,
+    /// Kills any actor that walks into it.
+    Pit,
+    /// Floor that slows down whoever stands on it.
+    Slow,
+    /// Blocks actors but lets bullets through.
+    HalfWall,
+}
+
+impl Tile {
+    /// The tile a level file means by `id`, or `None` if no tile has that id.
+    pub fn try_from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Tile::Floor),
+            1 => Some(Tile::Solid),
+            2 => Some(Tile::Destructible),
+            3 => Some(Tile::Pit),
+            4 => Some(Tile::Slow),
+            5 => Some(Tile::HalfWall),
+            _ => None,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Self {
+        Self::try_from_id(id).unwrap_or(Tile::Floor)
+    }
+
+    pub fn is_walkable(&self) -> bool {
+        matches!(self, Tile::Floor | Tile::Slow)
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct Pit;
+
+/// Multiplies the `MoveSpeed` of actors standing on it.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct SlowTerrain(pub f32);
+
+/// The speed modifier slow terrain put on an actor, and how many slow tiles it is touching.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Slowed {
+    pub stat_mod: Entity,
+    pub overlaps: usize,
+}
+
+pub(super) fn tiles_plugin(app: &mut App) {
+    app.register_type::<Tile>()
+        .register_type::<Pit>()
+        .register_type::<SlowTerrain>();
+    app.add_systems(
+        Update,
+        (
+            fall_into_pits,
+            (enter_slow_terrain, leave_slow_terrain).chain(),
+        ),
+    );
+}
+
+fn collision_pair<'a, T>(
+    a: Entity,
+    b: Entity,
+    tiles: &'a Query<&T>,
+) -> Option<(&'a T, Entity, Entity)>
+where
+    T: Component,
+{
+    match (tiles.get(a), tiles.get(b)) {
+        (Ok(tile), _) => Some((tile, a, b)),
+        (_, Ok(tile)) => Some((tile, b, a)),
+        _ => None,
+    }
+}
+
+pub fn fall_into_pits(
+    mut collisions: EventReader<CollisionStarted>,
+    pits: Query<&Pit>,
+    actors: Query<&Resource<Health>, With<Actor>>,
+    mut damage_events: EventWriter<DamageEvent>,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        let Some((_, pit, actor)) = collision_pair(*a, *b, &pits) else {
+            continue;
+        };
+        if let Ok(health) = actors.get(actor) {
+            damage_events.send(DamageEvent {
+                target: actor,
+                source: pit,
+                amount: health.current_value(),
+            });
+        }
+    }
+}
+
+pub fn enter_slow_terrain(
+    mut collisions: EventReader<CollisionStarted>,
+    terrain: Query<&SlowTerrain>,
+    mut actors: Query<(&mut Stat<MoveSpeed>, Option<&mut Slowed>), With<Actor>>,
+    mut commands: Commands,
+) {
+    // Actors slowed this frame don't have their `Slowed` yet, so a second tile touched in the same
+    // frame has to find it here.
+    let mut newly_slowed: HashMap<Entity, Slowed> = HashMap::new();
+    for CollisionStarted(a, b) in collisions.read() {
+        let Some((slow, _, actor)) = collision_pair(*a, *b, &terrain) else {
+            continue;
+        };
+        let Ok((mut speed, slowed)) = actors.get_mut(actor) else {
+            continue;
+        };
+        if let Some(mut slowed) = slowed {
+            slowed.overlaps += 1;
+        } else if let Some(slowed) = newly_slowed.get_mut(&actor) {
+            slowed.overlaps += 1;
+        } else {
+            let stat_mod = commands
+                .spawn((
+                    StatValueChange::<MoveSpeed>::new(slow.0, ModType::Multiplier),
+                    StatModifier,
+                ))
+                .id();
+            speed.add_mod(stat_mod);
+            newly_slowed.insert(
+                actor,
+                Slowed {
+                    stat_mod,
+                    overlaps: 1,
+                },
+            );
+        }
+    }
+    for (actor, slowed) in newly_slowed {
+        commands.entity(actor).insert(slowed);
+    }
+}
+
+pub fn leave_slow_terrain(
+    mut collisions: EventReader<CollisionEnded>,
+    terrain: Query<&SlowTerrain>,
+    mut actors: Query<&mut Slowed>,
+    mut deletions: EventWriter<DeleteStatMod>,
+    mut commands: Commands,
+) {
+    for CollisionEnded(a, b) in collisions.read() {
+        let Some((_, _, actor)) = collision_pair(*a, *b, &terrain) else {
+            continue;
+        };
+        let Ok(mut slowed) = actors.get_mut(actor) else {
+            continue;
+        };
+        slowed.overlaps = slowed.overlaps.saturating_sub(1);
+        if slowed.overlaps == 0 {
+            deletions.send(DeleteStatMod(slowed.stat_mod));
+            commands.entity(actor).remove::<Slowed>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{math::Vec2, prelude::Events};
+
+    use super::*;
+
+    #[test]
+    fn two_slow_tiles_in_one_frame_share_one_modifier() {
+        let mut app = App::new();
+        app.add_event::<CollisionStarted>()
+            .add_event::<CollisionEnded>()
+            .add_event::<DeleteStatMod>();
+        app.add_systems(Update, (enter_slow_terrain, leave_slow_terrain).chain());
+        let actor = app
+            .world_mut()
+            .spawn((
+                Actor {
+                    desired_direction: Vec2::ZERO,
+                    desired_target: None,
+                },
+                Stat::<MoveSpeed>::new(100.),
+            ))
+            .id();
+        let tiles = [
+            app.world_mut().spawn(SlowTerrain(0.5)).id(),
+            app.world_mut().spawn(SlowTerrain(0.5)).id(),
+        ];
+
+        for tile in tiles {
+            app.world_mut().send_event(CollisionStarted(tile, actor));
+        }
+        app.update();
+        let modifiers = app
+            .world_mut()
+            .query_filtered::<Entity, With<StatModifier>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(modifiers, 1);
+        assert_eq!(app.world().get::<Slowed>(actor).unwrap().overlaps, 2);
+
+        for tile in tiles {
+            app.world_mut().send_event(CollisionEnded(tile, actor));
+        }
+        app.update();
+        assert!(app.world().get::<Slowed>(actor).is_none());
+        assert_eq!(app.world().resource::<Events<DeleteStatMod>>().len(), 1);
+    }
+}
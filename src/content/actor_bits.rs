@@ -32,7 +32,14 @@ pub fn basic_actor() -> ComponentTree {
         Resource::<Health>::new(5.),
         CollisionLayers::new(
             GPL::Enemy,
-            [GPL::Enemy, GPL::Player, GPL::MapSolid, GPL::MapDynamic],
+            [
+                GPL::Enemy,
+                GPL::Player,
+                GPL::MapSolid,
+                GPL::MapDynamic,
+                GPL::Ethereal,
+                GPL::Trigger,
+            ],
         ),
     )
         .store()
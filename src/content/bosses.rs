@@ -0,0 +1,82 @@
+use avian2d::prelude::Mass;
+use bevy::transform::components::Transform;
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
+use bevy_stats::Stat;
+use std::f32::consts::TAU;
+
+use super::{enemies::hostile, projectile::basic_bullet};
+use crate::{
+    action_system::{
+        actions::{
+            spawn::{vel_spawn, vel_spawns},
+            telegraphed,
+        },
+        actuator::{actuator, ActuatorFireStyle},
+        triggers::timer::timer,
+    },
+    game::{
+        boss::{spin, sweep, Boss, BossPhase},
+        stats::{Damage, Knockback, MoveSpeed, ProjectileSpeed},
+    },
+    twin_stick::{
+        actors::Tracking,
+        ai::tracking::{TrackPlayer, TrackerAI},
+        weapons::Weapon,
+    },
+};
+
+/// A boss gun that fires every `interval` seconds, showing where its shots will go beforehand.
+fn emitter(interval: f32, damage: f32, speed: f32) -> ComponentTree {
+    (
+        Transform::default(),
+        Stat::<ProjectileSpeed>::new(speed),
+        Stat::<Damage>::new(damage),
+        Stat::<Knockback>::new(10.),
+        Weapon,
+    )
+        .store()
+        + actuator(ActuatorFireStyle::RisingEdge, 0.1)
+        + timer(interval)
+        + telegraphed()
+}
+
+/// `count` bullets out in every direction at once.
+pub fn bullet_ring(count: usize, interval: f32) -> ComponentTree {
+    emitter(interval, 1., 120.)
+        + vel_spawns((0..count).map(move |i| (basic_bullet(), TAU * i as f32 / count as f32)))
+        + name("Bullet Ring")
+}
+
+/// `arms` streams of bullets that wind round the boss at `speed` radians a second.
+pub fn spiral(arms: usize, interval: f32, speed: f32) -> ComponentTree {
+    emitter(interval, 0.5, 140.)
+        + spin(speed)
+        + vel_spawns((0..arms).map(move |i| (basic_bullet(), TAU * i as f32 / arms as f32)))
+        + name("Spiral")
+}
+
+/// A rapid stream of bolts swept across `arc` radians around the player.
+pub fn laser_sweep(arc: f32, speed: f32) -> ComponentTree {
+    ((Tracking(None), TrackPlayer, Transform::default()).store() + name("Laser Sweep"))
+        << (emitter(0.05, 0.25, 400.) + sweep(speed, arc) + vel_spawn(basic_bullet(), 0.))
+}
+
+pub fn warden() -> ComponentTree {
+    hostile(60.)
+        + (
+            Boss::new(
+                "The Warden",
+                vec![
+                    BossPhase::new(1., vec![bullet_ring(12, 2.5)]),
+                    BossPhase::new(0.6, vec![bullet_ring(16, 3.5), spiral(3, 0.2, 1.2)]),
+                    BossPhase::new(0.3, vec![spiral(4, 0.15, -1.8), laser_sweep(1.6, 1.2)]),
+                ],
+            ),
+            Mass(2000.),
+            Stat::<Damage>::new(2.),
+            Stat::<MoveSpeed>::new(25.),
+            TrackerAI { precision: 0.3 },
+        )
+            .store()
+        + name("The Warden")
+}
@@ -0,0 +1,66 @@
+use avian2d::prelude::Mass;
+use bevy::{core::Name, ecs::system::Res, transform::components::Transform};
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
+use bevy_stats::{Resource, Stat};
+
+use super::{actor_bits::basic_walker, projectile::basic_bullet};
+use crate::{
+    action_system::{
+        actions::spawn::{spawn, vel_spawn},
+        actuator::{Actuator, ActuatorFireStyle},
+        triggers::{propagation::ParentTrigger, proximity::ProximityTrigger},
+    },
+    assets::images::ImageResources,
+    game::stats::{Damage, Health, Knockback, ProjectileSpeed},
+    twin_stick::{
+        actors::{Tracking, MISC_ENEMY_FACTION},
+        ai::tracking::TrackHostiles,
+        deployables::Deployable,
+        player::Cursor,
+        projectile::Lifespan,
+        weapons::Weapon,
+    },
+};
+
+/// A stationary gun that shoots at the closest enemy for half a minute. Three at most. It takes
+/// the side of whoever put it down.
+pub fn sentry() -> ComponentTree {
+    (basic_walker(
+        ImageResources::placeholder_head,
+        ImageResources::placeholder_legs,
+    ) + (
+        Deployable::new("Sentry", 3),
+        Mass(10000.),
+        Resource::<Health>::new(5.),
+        Lifespan::new(30.),
+    )
+        .store()
+        + name("sentry"))
+        << ((
+            Tracking(None),
+            TrackHostiles,
+            Transform::default(),
+            Actuator::new(ActuatorFireStyle::Constantly, 0.8),
+            ProximityTrigger::new(1 << MISC_ENEMY_FACTION, 300.),
+            Stat::<ProjectileSpeed>::new(200.),
+            Stat::<Damage>::new(1.),
+            Stat::<Knockback>::new(10.),
+            Weapon,
+        )
+            .store()
+            + name("Sentry Gun")
+            + vel_spawn(basic_bullet(), 0.))
+}
+
+/// Puts a sentry down in front of the player.
+pub fn sentry_kit(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Sentry"))
+        << ((
+            Name::new("Deployer"),
+            Actuator::new(ActuatorFireStyle::SemiAuto(false), 2.),
+            Transform::from_xyz(0., 40., 0.),
+            ParentTrigger,
+        )
+            .store()
+            + spawn(sentry()))
+}
@@ -1,19 +1,30 @@
 use crate::{
+    action_system::{
+        actions::{dash::dash, heal::heal, sequence::sequence, spawn::spawn, telegraphed},
+        actuator::{Actuator, ActuatorFireStyle},
+        prefabs::spawn_delay,
+        triggers::proximity::ProximityTrigger,
+    },
     assets::images::ImageResources,
-    game::stats::{Damage, Health},
+    game::stats::{Damage, Health, MoveSpeed},
     twin_stick::{
-        actors::{Faction, MISC_ENEMY_FACTION},
+        actors::{Faction, MISC_ENEMY_FACTION, PLAYER_FACTION},
         ai::{tracking::TrackerAI, wander::PerlinWanderAI},
         physics::GamePhysicsLayer as GPL,
     },
 };
-use avian2d::prelude::CollisionLayers;
+use avian2d::prelude::{CollisionLayers, Mass};
+use bevy::transform::components::Transform;
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
 use bevy_stats::{Resource, Stat};
 
-use super::actor_bits::basic_walker;
+use super::{
+    actor_bits::basic_walker,
+    weapons::{scattergun, shotgun, sidearm, turret_gun},
+};
 
-pub fn stumbler() -> ComponentTree {
+/// A walker on the enemy side with `health` hit points and no behaviour yet.
+pub(super) fn hostile(health: f32) -> ComponentTree {
     basic_walker(
         ImageResources::placeholder_head,
         ImageResources::placeholder_legs,
@@ -26,14 +37,98 @@ pub fn stumbler() -> ComponentTree {
                 GPL::Enemy,
                 GPL::MapDynamic,
                 GPL::MapSolid,
+                GPL::Ethereal,
                 GPL::Bullet,
+                GPL::Trigger,
             ],
         ),
-        Resource::<Health>::new(3.),
-        Stat::<Damage>::new(2.),
-        TrackerAI { precision: 0.8 },
-        PerlinWanderAI::new(0.2, 0.8, 0.1, 0.95),
+        Resource::<Health>::new(health),
     )
         .store()
+}
+
+/// An action on an enemy that goes off every `cooldown` seconds while the player is in `range`.
+fn when_player_near(label: &'static str, range: f32, cooldown: f32) -> ComponentTree {
+    (
+        Transform::default(),
+        Actuator::new(ActuatorFireStyle::Constantly, cooldown),
+        ProximityTrigger::new(1 << PLAYER_FACTION, range),
+    )
+        .store()
+        + name(label)
+}
+
+pub fn stumbler() -> ComponentTree {
+    hostile(3.)
+        + (
+            Stat::<Damage>::new(2.),
+            TrackerAI { precision: 0.8 },
+            PerlinWanderAI::new(0.2, 0.8, 0.1, 0.95),
+        )
+            .store()
         + name("stumbler")
 }
+
+/// A small, quick stumbler that brood mothers hatch.
+pub fn mite() -> ComponentTree {
+    stumbler() + (Resource::<Health>::new(1.), Stat::<MoveSpeed>::new(100.)).store() + name("mite")
+}
+
+/// Stands its ground and shoots at the player once they come close.
+pub fn turret() -> ComponentTree {
+    (hostile(6.) + (Mass(10000.), Stat::<Damage>::new(1.)).store() + name("turret")) << turret_gun()
+}
+
+pub fn shotgunner() -> ComponentTree {
+    (hostile(4.)
+        + (
+            Stat::<Damage>::new(1.),
+            Stat::<MoveSpeed>::new(50.),
+            TrackerAI { precision: 0.4 },
+            PerlinWanderAI::new(0.2, 0.8, 0.1, 0.95),
+        )
+            .store()
+        + name("shotgunner"))
+        << shotgun()
+}
+
+/// Slowly wanders about and hatches mites while the player is around.
+pub fn brood_mother() -> ComponentTree {
+    (hostile(8.)
+        + (
+            Stat::<MoveSpeed>::new(30.),
+            PerlinWanderAI::new(0.2, 0.8, 0.1, 0.95),
+        )
+            .store()
+        + name("brood mother"))
+        << (when_player_near("Brood", 400., 6.) + spawn(spawn_delay(1., mite()) + telegraphed()))
+}
+
+/// Hangs back and patches up the enemies around it.
+pub fn healer() -> ComponentTree {
+    (hostile(4.)
+        + (
+            Stat::<Damage>::new(1.),
+            Stat::<MoveSpeed>::new(50.),
+            TrackerAI { precision: 0.2 },
+            PerlinWanderAI::new(0.2, 0.8, 0.1, 0.95),
+        )
+            .store()
+        + name("healer"))
+        << (when_player_near("Mending Aura", 400., 4.) + heal(150., 1.))
+        << sidearm()
+}
+
+/// Closes in on the player and lunges at them after a short wind-up.
+pub fn charger() -> ComponentTree {
+    (hostile(5.)
+        + (
+            Stat::<Damage>::new(2.),
+            Stat::<MoveSpeed>::new(60.),
+            TrackerAI { precision: 1. },
+        )
+            .store()
+        + name("charger"))
+        << (when_player_near("Charge", 300., 2.5) + sequence(vec![(0.4, dash(60000.))]))
+        << scattergun()
+}
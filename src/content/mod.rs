@@ -1,6 +1,10 @@
 pub mod actor_bits;
+pub mod bosses;
+pub mod deployables;
 pub mod enemies;
+pub mod modifiers;
 pub mod player;
 pub mod projectile;
 pub mod util;
+pub mod waves;
 pub mod weapons;
@@ -0,0 +1,44 @@
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
+
+use crate::{
+    game::modifiers::{PayloadPatch, WeaponModifier},
+    twin_stick::projectile::Explosive,
+};
+
+pub fn faster() -> ComponentTree {
+    WeaponModifier {
+        cooldown: 0.5,
+        damage: 0.75,
+        ..Default::default()
+    }
+    .store()
+        + name("Faster")
+}
+
+pub fn aoe() -> ComponentTree {
+    WeaponModifier {
+        payload: vec![PayloadPatch::Append(
+            Explosive {
+                radius: 60.,
+                damage: 1.,
+            }
+            .store(),
+        )],
+        ..Default::default()
+    }
+    .store()
+        + name("AoE")
+}
+
+pub fn dubler() -> ComponentTree {
+    WeaponModifier {
+        damage: 0.6,
+        payload: vec![PayloadPatch::Multiply {
+            copies: 2,
+            spread: 0.15,
+        }],
+        ..Default::default()
+    }
+    .store()
+        + name("Dubler")
+}
@@ -1,6 +1,8 @@
 use crate::{
+    arena::PlayerStart,
     assets::images::ImageResources,
-    game::stats::MoveSpeed,
+    controls::ControlBindings,
+    game::{equipment::Equipment, inventory::Inventory, stats::MoveSpeed},
     twin_stick::{
         actors::{Faction, PLAYER_FACTION},
         ai::keyboard::{create_player_action_input_manager_bundle, KeyboardAI},
@@ -10,7 +12,10 @@ use crate::{
     util::image,
 };
 use avian2d::prelude::CollisionLayers;
-use bevy::ecs::system::{Commands, Res};
+use bevy::{
+    ecs::system::{Commands, Res},
+    transform::components::Transform,
+};
 use bevy_composable::{
     app_impl::{ComplexSpawnable, ComponentTreeable},
     tree::ComponentTree,
@@ -21,19 +26,33 @@ use bevy_stats::Stat;
 use super::{
     actor_bits::{basic_actor, basic_head, basic_legs},
     util::tracking,
-    weapons::peashooter,
+    weapons::{dash_boots, peashooter},
 };
 
-pub fn spawn_player(mut commands: Commands, cursor: Res<Cursor>) {
-    let player_id = commands.compose(player_tree(&cursor) << peashooter(&cursor));
-    commands
-        .get_entity(player_id)
-        .unwrap()
-        .insert(create_player_action_input_manager_bundle());
+pub fn spawn_player(
+    mut commands: Commands,
+    cursor: Res<Cursor>,
+    bindings: Res<ControlBindings>,
+    start: Option<Res<PlayerStart>>,
+) {
+    let player_id = commands.compose(player_tree(&cursor));
+    let start = start.map(|w| w.0).unwrap_or_default();
+    let mut equipment = Equipment::default();
+    equipment
+        .equip(&mut commands, player_id, 0, peashooter(&cursor))
+        .expect("slot 0 is a valid equipment slot");
+    equipment
+        .equip(&mut commands, player_id, 2, dash_boots(&cursor))
+        .expect("slot 2 is a valid equipment slot");
+    commands.get_entity(player_id).unwrap().insert((
+        create_player_action_input_manager_bundle(&bindings),
+        equipment,
+        Transform::from_translation(start.extend(0.)),
+    ));
 }
 
 fn player_tree_base() -> ComponentTree {
-    (Player, KeyboardAI).store()
+    (Player, KeyboardAI, Inventory::default()).store()
         + Stat::<MoveSpeed>::new(80.).store()
         + Faction(PLAYER_FACTION).store()
         + CollisionLayers::new(
@@ -43,7 +62,9 @@ fn player_tree_base() -> ComponentTree {
                 GPL::Enemy,
                 GPL::MapSolid,
                 GPL::MapDynamic,
-                GPL::Bullet,
+                GPL::Ethereal,
+                GPL::EnemyBullet,
+                GPL::Trigger,
             ],
         )
         .store()
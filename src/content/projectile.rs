@@ -1,26 +1,11 @@
 use crate::{
     graphics::rect,
-    twin_stick::{
-        physics::GamePhysicsLayer,
-        projectile::{projectile, Projectile},
-    },
+    twin_stick::projectile::{projectile, Projectile},
 };
-use avian2d::prelude::CollisionLayers;
 use bevy::prelude::Color;
-use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_composable::tree::ComponentTree;
 
+/// Takes its collision layers from the side of whoever fires it.
 pub fn basic_bullet() -> ComponentTree {
     projectile(1., Projectile::default()) + rect(0., 0., 10., 10., Color::srgb(0., 0., 0.))
 }
-
-pub fn standard_player_bullet_collision() -> ComponentTree {
-    CollisionLayers::new(
-        GamePhysicsLayer::Bullet,
-        [
-            GamePhysicsLayer::Enemy,
-            GamePhysicsLayer::MapSolid,
-            GamePhysicsLayer::MapDynamic,
-        ],
-    )
-    .store()
-}
@@ -0,0 +1,58 @@
+use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree};
+use bevy_stats::Stat;
+
+use super::{
+    bosses::warden,
+    enemies::{brood_mother, charger, healer, shotgunner, stumbler, turret},
+};
+use crate::game::{
+    director::{Wave, WaveEntry},
+    run::NodeKind,
+    stats::MoveSpeed,
+};
+
+fn sprinter() -> ComponentTree {
+    stumbler() + Stat::<MoveSpeed>::new(110.).store()
+}
+
+/// The waves for an arena of `kind` at `depth` on the run map. Deeper arenas get more and bigger
+/// waves with tougher archetypes. Elites and bosses get an extra one, which ends with a brood mother
+/// or the boss itself.
+pub fn waves_for(kind: NodeKind, depth: usize) -> Vec<Wave> {
+    let count = 2 + depth / 2 + if kind == NodeKind::Arena { 0 } else { 1 };
+    (0..count)
+        .map(|wave| {
+            let mut entries = vec![WaveEntry::new(
+                stumbler(),
+                2 + wave as u32 + depth as u32,
+                0.6,
+            )];
+            if wave > 0 || depth > 1 {
+                entries.push(WaveEntry::new(
+                    sprinter(),
+                    wave as u32 + depth as u32 / 2,
+                    1.2,
+                ));
+            }
+            let extra = wave as u32 + depth as u32;
+            if depth > 0 {
+                entries.push(WaveEntry::new(turret(), 1 + extra / 3, 0.8));
+            }
+            if wave > 0 {
+                entries.push(WaveEntry::new(shotgunner(), extra / 2, 1.));
+            }
+            if depth > 1 {
+                entries.push(WaveEntry::new(charger(), extra / 3, 1.5));
+                entries.push(WaveEntry::new(healer(), 1, 0.));
+            }
+            if wave + 1 == count {
+                match kind {
+                    NodeKind::Arena => (),
+                    NodeKind::Elite => entries.push(WaveEntry::new(brood_mother(), 1, 0.)),
+                    NodeKind::Boss => entries.push(WaveEntry::new(warden(), 1, 0.)),
+                }
+            }
+            Wave::new(entries)
+        })
+        .collect()
+}
@@ -1,27 +1,33 @@
+use avian2d::prelude::LayerMask;
 use bevy::{core::Name, ecs::system::Res, transform::components::Transform};
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
 use bevy_stats::Stat;
 
 use crate::{
     action_system::{
-        actions::vel_spawn::vel_spawn,
+        actions::{
+            dash::DashAction,
+            hitscan::{beam, hitscan},
+            melee::{melee, HitboxShape, MeleeAction},
+            spawn::{vel_spawn, vel_spawns},
+        },
         actuator::{Actuator, ActuatorFireStyle},
-        triggers::{key_action::PlayerActionTrigger, propagation::ParentTrigger},
+        triggers::{propagation::ParentTrigger, proximity::ProximityTrigger},
     },
     game::stats::{Damage, Knockback, ProjectileSpeed},
-    twin_stick::{actors::Tracking, ai::keyboard::PlayerAction, player::Cursor, weapons::Weapon},
+    twin_stick::{
+        actors::{Tracking, PLAYER_FACTION},
+        ai::tracking::TrackPlayer,
+        physics::GamePhysicsLayer as GPL,
+        player::Cursor,
+        weapons::{StunOnHit, Weapon},
+    },
 };
 
-use super::projectile::{basic_bullet, standard_player_bullet_collision};
+use super::projectile::basic_bullet;
 
 pub fn peashooter(cursor: &Res<Cursor>) -> ComponentTree {
-    ((
-        PlayerActionTrigger::new([PlayerAction::Shoot1]),
-        Tracking(Some(cursor.0)),
-        Transform::default(),
-    )
-        .store()
-        + name("Peashooter"))
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Peashooter"))
         << ((
             Name::new("Barrel"),
             Actuator::new(ActuatorFireStyle::SemiAuto(false), 1.3),
@@ -33,5 +39,143 @@ pub fn peashooter(cursor: &Res<Cursor>) -> ComponentTree {
             Weapon,
         )
             .store()
-            + vel_spawn(basic_bullet() + standard_player_bullet_collision(), 0.))
+            + vel_spawn(basic_bullet(), 0.))
+}
+
+pub fn wristgun(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Wrist Gun"))
+        << ((
+            Name::new("Barrel"),
+            Actuator::new(ActuatorFireStyle::SemiAuto(false), 0.65),
+            Stat::<ProjectileSpeed>::new(200.),
+            Stat::<Damage>::new(0.5),
+            Stat::<Knockback>::new(10.),
+            Transform::from_xyz(0., 20., 0.),
+            ParentTrigger,
+            Weapon,
+        )
+            .store()
+            + vel_spawn(basic_bullet(), 0.))
+}
+
+/// A wide swing that slows the wielder while it winds up, bats bullets back and briefly stuns
+/// what it hits.
+pub fn cleaver(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Cleaver"))
+        << ((
+            Name::new("Blade"),
+            Actuator::new(ActuatorFireStyle::SemiAuto(false), 0.6),
+            Stat::<Damage>::new(3.),
+            Stat::<Knockback>::new(40.),
+            StunOnHit(0.4),
+            Transform::default(),
+            ParentTrigger,
+            Weapon,
+        )
+            .store()
+            + melee(MeleeAction {
+                self_slow: 0.3,
+                reflect: true,
+                ..MeleeAction::new(
+                    HitboxShape::Arc {
+                        radius: 50.,
+                        angle: 2.,
+                    },
+                    10.,
+                    0.2,
+                    0.15,
+                )
+            }))
+}
+
+/// Hits instantly and goes through two targets.
+pub fn railgun(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Railgun"))
+        << ((
+            Name::new("Barrel"),
+            Actuator::new(ActuatorFireStyle::SemiAuto(false), 1.5),
+            Stat::<Damage>::new(3.),
+            Stat::<Knockback>::new(20.),
+            Transform::from_xyz(0., 20., 0.),
+            ParentTrigger,
+            Weapon,
+        )
+            .store()
+            + hitscan(600., 2))
+}
+
+/// A beam that burns whatever it touches for as long as the trigger is held.
+pub fn lance(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Lance"))
+        << ((
+            Name::new("Emitter"),
+            Actuator::new(ActuatorFireStyle::Constantly, 0.),
+            Stat::<Damage>::new(4.),
+            Transform::from_xyz(0., 20., 0.),
+            ParentTrigger,
+            Weapon,
+        )
+            .store()
+            + beam(250., 0))
+}
+
+/// Throws the player the way they are moving, or at the cursor when standing still, slipping
+/// through enemies and their bullets on the way.
+pub fn dash_boots(cursor: &Res<Cursor>) -> ComponentTree {
+    ((Tracking(Some(cursor.0)), Transform::default()).store() + name("Dash"))
+        << ((
+            Name::new("Boots"),
+            Actuator::new(ActuatorFireStyle::SemiAuto(false), 0.8),
+            Transform::default(),
+            ParentTrigger,
+            DashAction {
+                invulnerable: 0.25,
+                pass_through: LayerMask::from([GPL::Enemy, GPL::EnemyBullet]),
+                ..DashAction::new(50000.)
+            },
+        )
+            .store())
+}
+
+/// An enemy gun that aims at the player and fires whenever they are within `range`.
+fn enemy_gun(label: &'static str, range: f32, cooldown: f32, damage: f32) -> ComponentTree {
+    (
+        Tracking(None),
+        TrackPlayer,
+        Transform::default(),
+        Actuator::new(ActuatorFireStyle::Constantly, cooldown),
+        ProximityTrigger::new(1 << PLAYER_FACTION, range),
+        Stat::<ProjectileSpeed>::new(150.),
+        Stat::<Damage>::new(damage),
+        Stat::<Knockback>::new(10.),
+        Weapon,
+    )
+        .store()
+        + name(label)
+}
+
+pub fn turret_gun() -> ComponentTree {
+    enemy_gun("Turret Gun", 350., 1.2, 1.) + vel_spawn(basic_bullet(), 0.)
+}
+
+pub fn shotgun() -> ComponentTree {
+    enemy_gun("Shotgun", 220., 2., 0.5)
+        + vel_spawns(
+            [-0.4, -0.2, 0., 0.2, 0.4]
+                .into_iter()
+                .map(|w: f32| (basic_bullet(), w)),
+        )
+}
+
+pub fn scattergun() -> ComponentTree {
+    enemy_gun("Scattergun", 140., 1.4, 0.5)
+        + vel_spawns(
+            [-0.25, 0., 0.25]
+                .into_iter()
+                .map(|w: f32| (basic_bullet(), w)),
+        )
+}
+
+pub fn sidearm() -> ComponentTree {
+    enemy_gun("Sidearm", 300., 2.5, 0.5) + vel_spawn(basic_bullet(), 0.)
 }
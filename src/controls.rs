@@ -0,0 +1,247 @@
+use bevy::{
+    app::{App, Plugin, Update},
+    input::{keyboard::KeyCode, mouse::MouseButton, ButtonInput},
+    log::warn,
+    prelude::{resource_changed, IntoSystemConfigs, Query, Res, ResMut, Resource},
+};
+use leafwing_input_manager::prelude::{InputMap, VirtualDPad};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, error::Error, fs, path::PathBuf};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::twin_stick::ai::keyboard::PlayerAction;
+
+const CONFIG_DIR: &str = "lockstockbarrel";
+const CONTROLS_FILE: &str = "controls.ron";
+
+/// A single rebindable input. `Walk` is split into its four directions.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter,
+)]
+pub enum BindingSlot {
+    WalkUp,
+    WalkDown,
+    WalkLeft,
+    WalkRight,
+    Shoot1,
+    Shoot2,
+    Shoot3,
+    Shoot4,
+}
+
+impl BindingSlot {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BindingSlot::WalkUp => "Walk Up",
+            BindingSlot::WalkDown => "Walk Down",
+            BindingSlot::WalkLeft => "Walk Left",
+            BindingSlot::WalkRight => "Walk Right",
+            BindingSlot::Shoot1 => "Shoot 1",
+            BindingSlot::Shoot2 => "Shoot 2",
+            BindingSlot::Shoot3 => "Shoot 3",
+            BindingSlot::Shoot4 => "Shoot 4",
+        }
+    }
+
+    /// Walk directions feed a `VirtualDPad`, which we only build from keys.
+    pub fn takes_mouse(&self) -> bool {
+        !matches!(
+            self,
+            BindingSlot::WalkUp
+                | BindingSlot::WalkDown
+                | BindingSlot::WalkLeft
+                | BindingSlot::WalkRight
+        )
+    }
+
+    pub fn accepts(&self, binding: &Binding) -> bool {
+        self.takes_mouse() || matches!(binding, Binding::Key(_))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl std::fmt::Display for Binding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "{:?}", key),
+            Binding::Mouse(button) => write!(f, "Mouse {:?}", button),
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ControlBindings {
+    pub bindings: BTreeMap<BindingSlot, Binding>,
+}
+
+impl Default for ControlBindings {
+    fn default() -> Self {
+        Self {
+            bindings: BTreeMap::from([
+                (BindingSlot::WalkUp, Binding::Key(KeyCode::KeyW)),
+                (BindingSlot::WalkDown, Binding::Key(KeyCode::KeyS)),
+                (BindingSlot::WalkLeft, Binding::Key(KeyCode::KeyA)),
+                (BindingSlot::WalkRight, Binding::Key(KeyCode::KeyD)),
+                (BindingSlot::Shoot1, Binding::Mouse(MouseButton::Left)),
+                (BindingSlot::Shoot2, Binding::Mouse(MouseButton::Right)),
+                (BindingSlot::Shoot3, Binding::Key(KeyCode::ShiftLeft)),
+                (BindingSlot::Shoot4, Binding::Key(KeyCode::KeyF)),
+            ]),
+        }
+    }
+}
+
+impl ControlBindings {
+    pub fn get(&self, slot: BindingSlot) -> Binding {
+        self.bindings
+            .get(&slot)
+            .copied()
+            .unwrap_or_else(|| ControlBindings::default().bindings[&slot])
+    }
+
+    /// Bind `slot` to `binding`, unless the slot doesn't accept it. Returns whether it was bound.
+    pub fn set(&mut self, slot: BindingSlot, binding: Binding) -> bool {
+        let accepted = slot.accepts(&binding);
+        if accepted {
+            self.bindings.insert(slot, binding);
+        }
+        accepted
+    }
+
+    /// Every slot that shares its binding with at least one other slot.
+    pub fn conflicts(&self) -> Vec<BindingSlot> {
+        BindingSlot::iter()
+            .filter(|slot| {
+                BindingSlot::iter()
+                    .any(|other| other != *slot && self.get(other) == self.get(*slot))
+            })
+            .collect()
+    }
+
+    pub fn input_map(&self) -> InputMap<PlayerAction> {
+        let walk = [
+            BindingSlot::WalkUp,
+            BindingSlot::WalkDown,
+            BindingSlot::WalkLeft,
+            BindingSlot::WalkRight,
+        ]
+        .map(|slot| match self.get(slot) {
+            Binding::Key(key) => Some(key),
+            Binding::Mouse(_) => None,
+        });
+        let mut map = InputMap::default();
+        // `set` and `load` keep mouse buttons off the walk slots, so this always matches.
+        if let [Some(up), Some(down), Some(left), Some(right)] = walk {
+            map = map.with_dual_axis(PlayerAction::Walk, VirtualDPad::new(up, down, left, right));
+        }
+        for (slot, action) in [
+            (BindingSlot::Shoot1, PlayerAction::Shoot1),
+            (BindingSlot::Shoot2, PlayerAction::Shoot2),
+            (BindingSlot::Shoot3, PlayerAction::Shoot3),
+            (BindingSlot::Shoot4, PlayerAction::Shoot4),
+        ] {
+            match self.get(slot) {
+                Binding::Key(key) => map.insert(action, key),
+                Binding::Mouse(button) => map.insert(action, button),
+            };
+        }
+        map
+    }
+
+    pub fn config_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|w| w.join(CONFIG_DIR).join(CONTROLS_FILE))
+    }
+
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let path = Self::config_path().ok_or("No config directory on this platform")?;
+        let loaded: Self = ron::from_str(&fs::read_to_string(path)?)?;
+        if let Some((slot, binding)) = loaded.bindings.iter().find(|(k, v)| !k.accepts(v)) {
+            return Err(format!("{} can't be bound to {}", slot.label(), binding).into());
+        }
+        Ok(loaded)
+    }
+
+    pub fn load_or_default() -> Self {
+        match Self::load() {
+            Ok(bindings) => bindings,
+            Err(e) => {
+                warn!("Using default controls: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = Self::config_path().ok_or("No config directory on this platform")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            path,
+            ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?,
+        )?;
+        Ok(())
+    }
+}
+
+/// The slot currently waiting for the player to press something, if any.
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rebinding(pub Option<BindingSlot>);
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ControlBindings::load_or_default());
+        app.init_resource::<Rebinding>();
+
+        app.add_systems(
+            Update,
+            (
+                capture_rebind,
+                apply_bindings_to_players.run_if(resource_changed::<ControlBindings>),
+            )
+                .chain(),
+        );
+    }
+}
+
+pub fn capture_rebind(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<ControlBindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+) {
+    let Some(slot) = rebinding.0 else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::Escape) {
+        rebinding.0 = None;
+        return;
+    }
+    let pressed = keys
+        .get_just_pressed()
+        .map(|w| Binding::Key(*w))
+        .chain(mouse.get_just_pressed().map(|w| Binding::Mouse(*w)))
+        .find(|w| slot.accepts(w));
+    if let Some(binding) = pressed {
+        if bindings.set(slot, binding) {
+            rebinding.0 = None;
+        }
+    }
+}
+
+pub fn apply_bindings_to_players(
+    bindings: Res<ControlBindings>,
+    mut maps: Query<&mut InputMap<PlayerAction>>,
+) {
+    for mut map in maps.iter_mut() {
+        *map = bindings.input_map();
+    }
+}
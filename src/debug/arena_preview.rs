@@ -0,0 +1,166 @@
+use bevy::{
+    input::{keyboard::KeyCode, ButtonInput},
+    prelude::{Res, ResMut, Resource},
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::arena::{generate_arena, props::PropKind, ArenaGenConfig, GeneratedArena, Tile};
+
+/// Window for level designers to tweak `ArenaGenConfig` and look at the arena a seed produces.
+#[derive(Resource, Default)]
+pub struct ArenaPreview {
+    pub open: bool,
+    pub seed: u64,
+    cached: Option<(u64, ArenaGenConfig, GeneratedArena)>,
+}
+
+pub(super) fn toggle_arena_preview(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut preview: ResMut<ArenaPreview>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        preview.open = !preview.open;
+    }
+}
+
+pub(super) fn arena_preview_gui(
+    mut root: EguiContexts,
+    mut preview: ResMut<ArenaPreview>,
+    mut config: ResMut<ArenaGenConfig>,
+) {
+    if !preview.open {
+        return;
+    }
+    let mut open = preview.open;
+    let mut seed = preview.seed;
+    let mut tuned = config.clone();
+
+    egui::Window::new("Arena Generator")
+        .open(&mut open)
+        .resizable(false)
+        .show(root.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Seed");
+                ui.add(egui::DragValue::new(&mut seed));
+                if ui.button("<").clicked() {
+                    seed = seed.wrapping_sub(1);
+                }
+                if ui.button(">").clicked() {
+                    seed = seed.wrapping_add(1);
+                }
+            });
+            egui::Grid::new("arena_config").show(ui, |ui| {
+                ui.label("Size");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut tuned.width).range(8..=256));
+                    ui.add(egui::DragValue::new(&mut tuned.height).range(8..=256));
+                });
+                ui.end_row();
+                ui.label("Fill chance");
+                ui.add(egui::Slider::new(&mut tuned.fill_chance, 0.0..=0.8));
+                ui.end_row();
+                ui.label("Smoothing steps");
+                ui.add(egui::Slider::new(&mut tuned.smoothing_steps, 0..=10));
+                ui.end_row();
+                ui.label("Birth limit");
+                ui.add(egui::Slider::new(&mut tuned.birth_limit, 0..=8));
+                ui.end_row();
+                ui.label("Death limit");
+                ui.add(egui::Slider::new(&mut tuned.death_limit, 0..=8));
+                ui.end_row();
+                ui.label("Min open ratio");
+                ui.add(egui::Slider::new(&mut tuned.min_open_ratio, 0.0..=0.8));
+                ui.end_row();
+                ui.label("Spawners");
+                ui.add(egui::Slider::new(&mut tuned.spawner_count, 0..=16));
+                ui.end_row();
+                ui.label("Spawner distance");
+                ui.add(egui::Slider::new(&mut tuned.min_spawner_distance, 0..=40));
+                ui.end_row();
+                ui.label("Max difficulty");
+                ui.add(egui::Slider::new(&mut tuned.max_difficulty, 1..=5));
+                ui.end_row();
+                ui.label("Props");
+                ui.add(egui::Slider::new(&mut tuned.prop_count, 0..=40));
+                ui.end_row();
+            });
+            if ui.button("Defaults").clicked() {
+                tuned = ArenaGenConfig::default();
+            }
+            ui.separator();
+
+            let stale = preview
+                .cached
+                .as_ref()
+                .is_none_or(|(s, c, _)| *s != seed || *c != tuned);
+            if stale {
+                preview.cached = Some((seed, tuned.clone(), generate_arena(seed, &tuned)));
+            }
+            let (_, _, arena) = preview.cached.as_ref().unwrap();
+            draw_arena(ui, arena);
+        });
+
+    preview.open = open;
+    preview.seed = seed;
+    if *config != tuned {
+        *config = tuned;
+    }
+}
+
+fn draw_arena(ui: &mut egui::Ui, arena: &GeneratedArena) {
+    let rows = arena.map.len().max(1);
+    let columns = arena.map.iter().map(|w| w.len()).max().unwrap_or(1).max(1);
+    let cell_size = (400. / columns.max(rows) as f32).max(1.);
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(cell_size * columns as f32, cell_size * rows as f32),
+        egui::Sense::hover(),
+    );
+    let origin = response.rect.min;
+    let rect = |(y, x): (usize, usize)| {
+        egui::Rect::from_min_size(
+            origin + egui::vec2(x as f32 * cell_size, y as f32 * cell_size),
+            egui::vec2(cell_size, cell_size),
+        )
+    };
+
+    for (y, row) in arena.map.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let color = match tile {
+                Tile::Floor => egui::Color32::from_gray(180),
+                Tile::Solid => egui::Color32::from_rgb(64, 64, 190),
+                Tile::Destructible => egui::Color32::from_rgb(140, 100, 64),
+                Tile::Pit => egui::Color32::from_gray(12),
+                Tile::Slow => egui::Color32::from_rgb(100, 140, 76),
+                Tile::HalfWall => egui::Color32::from_rgb(128, 150, 204),
+            };
+            painter.rect_filled(rect((y, x)), 0., color);
+        }
+    }
+    for (cell, kind) in arena.props.iter() {
+        let color = match kind {
+            PropKind::Crate => egui::Color32::from_rgb(178, 128, 76),
+            PropKind::Barrel => egui::Color32::from_rgb(204, 50, 25),
+            PropKind::Cover => egui::Color32::from_rgb(115, 115, 128),
+        };
+        painter.rect_filled(rect(*cell).shrink(cell_size * 0.2), 0., color);
+    }
+    painter.rect_filled(rect(arena.player_start), 0., egui::Color32::GREEN);
+    painter.rect_filled(rect(arena.exit), 0., egui::Color32::YELLOW);
+    for spawner in arena.spawners.iter() {
+        let shade = (255 / spawner.difficulty.max(1)) as u8;
+        painter.rect_filled(
+            rect(spawner.cell),
+            0.,
+            egui::Color32::from_rgb(255, shade, 0),
+        );
+    }
+    ui.label(format!(
+        "{} spawners, difficulties {:?}",
+        arena.spawners.len(),
+        arena
+            .spawners
+            .iter()
+            .map(|w| w.difficulty)
+            .collect::<Vec<_>>()
+    ));
+}
@@ -1,3 +1,4 @@
+use arena_preview::{arena_preview_gui, toggle_arena_preview, ArenaPreview};
 use arrows::{display_arrows, Arrows};
 use bevy::{
     app::{Plugin, Update},
@@ -17,6 +18,7 @@ use crate::action_system::{
 };
 
 pub struct DebugPlugin;
+pub mod arena_preview;
 pub mod arrows;
 pub mod grid;
 
@@ -25,12 +27,14 @@ impl Plugin for DebugPlugin {
         // #[cfg(feature = "editor")]
         app.add_plugins(EditorPlugin::new());
         app.init_resource::<Arrows>();
+        app.init_resource::<ArenaPreview>();
         //    .insert_resource(default_editor_controls());
 
         #[cfg(feature = "physdebug")]
         app.add_plugin(RapierDebugRenderPlugin::default());
 
         app.add_systems(Update, (test_display, grid_system, display_arrows));
+        app.add_systems(Update, (toggle_arena_preview, arena_preview_gui).chain());
     }
 }
 
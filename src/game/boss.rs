@@ -0,0 +1,200 @@
+use bevy::{
+    app::{App, Update},
+    hierarchy::{BuildChildren, Children, DespawnRecursiveExt},
+    math::Quat,
+    prelude::{
+        in_state, Commands, Component, Entity, IntoSystemConfigs, Query, Res, Transform, With,
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_composable::{
+    app_impl::{ComplexSpawnable, ComponentTreeable},
+    tree::ComponentTree,
+};
+use bevy_stats::Resource as StatResource;
+
+use super::stats::{Health, ResourceMax};
+use crate::states::{AppState, GameState, TimerState};
+
+/// One stage of a boss fight. It starts once the boss is down to `threshold` of its health and
+/// swaps whatever attacks the previous phase had for `attacks`.
+#[derive(Clone)]
+pub struct BossPhase {
+    pub threshold: f32,
+    pub attacks: Vec<ComponentTree>,
+}
+
+impl BossPhase {
+    pub fn new(threshold: f32, attacks: Vec<ComponentTree>) -> Self {
+        Self { threshold, attacks }
+    }
+}
+
+/// A boss and the phases of its fight, from full health down. Health fractions are taken
+/// against the boss's `ResourceMax<Health>`.
+#[derive(Component, Clone)]
+pub struct Boss {
+    pub name: String,
+    pub phases: Vec<BossPhase>,
+    pub current: Option<usize>,
+}
+
+impl Boss {
+    pub fn new(name: &str, phases: Vec<BossPhase>) -> Self {
+        Self {
+            name: name.to_string(),
+            phases,
+            current: None,
+        }
+    }
+
+    /// The phase the boss should be in at `fraction` of its health.
+    pub fn phase_at(&self, fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|w| fraction <= w.threshold)
+            .unwrap_or(0)
+    }
+}
+
+/// A child the current phase gave the boss, taken away again when the phase ends.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct BossAttack;
+
+/// Turns an attack emitter at `speed` radians a second. With an `arc` it sweeps back and forth
+/// across it instead of going round.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+pub struct Spin {
+    pub speed: f32,
+    pub arc: Option<f32>,
+    pub angle: f32,
+}
+
+impl Spin {
+    pub fn new(speed: f32) -> Self {
+        Self {
+            speed,
+            arc: None,
+            angle: 0.,
+        }
+    }
+
+    pub fn sweep(speed: f32, arc: f32) -> Self {
+        Self {
+            speed,
+            arc: Some(arc),
+            angle: 0.,
+        }
+    }
+}
+
+pub fn spin(speed: f32) -> ComponentTree {
+    Spin::new(speed).store()
+}
+
+pub fn sweep(speed: f32, arc: f32) -> ComponentTree {
+    Spin::sweep(speed, arc).store()
+}
+
+pub(super) fn boss_plugin(app: &mut App) {
+    app.register_type::<BossAttack>().register_type::<Spin>();
+    app.add_systems(
+        Update,
+        (advance_boss_phases, spin_emitters)
+            .run_if(in_state(TimerState::Playing))
+            .run_if(in_state(GameState::InLevel))
+            .run_if(in_state(AppState::Game)),
+    );
+}
+
+/// Phases only ever move forward, so a boss that gets healed keeps its current attacks.
+pub fn advance_boss_phases(
+    mut bosses: Query<(
+        Entity,
+        &mut Boss,
+        &StatResource<Health>,
+        &ResourceMax<Health>,
+        Option<&Children>,
+    )>,
+    attacks: Query<(), With<BossAttack>>,
+    mut commands: Commands,
+) {
+    for (entity, mut boss, health, max, children) in bosses.iter_mut() {
+        let phase = boss
+            .phase_at(health.current_value() / max.value.max(1.))
+            .max(boss.current.unwrap_or(0));
+        if boss.current == Some(phase) || phase >= boss.phases.len() {
+            continue;
+        }
+        for child in children
+            .into_iter()
+            .flatten()
+            .filter(|w| attacks.contains(**w))
+        {
+            commands.entity(*child).despawn_recursive();
+        }
+        for attack in boss.phases[phase].attacks.iter() {
+            let attack = commands.compose(attack.clone() + BossAttack.store());
+            commands.entity(entity).add_child(attack);
+        }
+        boss.current = Some(phase);
+    }
+}
+
+pub fn spin_emitters(mut emitters: Query<(&mut Spin, &mut Transform)>, time: Res<Time>) {
+    for (mut spin, mut transform) in emitters.iter_mut() {
+        spin.angle += spin.speed * time.delta().as_secs_f32();
+        if let Some(arc) = spin.arc {
+            if spin.angle.abs() > arc / 2. {
+                spin.angle = spin.angle.clamp(-arc / 2., arc / 2.);
+                spin.speed = -spin.speed;
+            }
+        }
+        transform.rotation = Quat::from_rotation_z(spin.angle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn three_phases() -> Boss {
+        Boss::new(
+            "Test",
+            vec![
+                BossPhase::new(1., vec![]),
+                BossPhase::new(0.6, vec![]),
+                BossPhase::new(0.3, vec![]),
+            ],
+        )
+    }
+
+    #[test]
+    fn phase_follows_health_fraction() {
+        let boss = three_phases();
+        assert_eq!(boss.phase_at(1.), 0);
+        assert_eq!(boss.phase_at(0.5), 1);
+        assert_eq!(boss.phase_at(0.1), 2);
+    }
+
+    #[test]
+    fn healing_does_not_go_back_a_phase() {
+        let mut app = App::new();
+        app.add_systems(Update, advance_boss_phases);
+        let boss = app
+            .world_mut()
+            .spawn((
+                StatResource::<Health>::new(100.),
+                ResourceMax::<Health>::new(100.),
+                three_phases(),
+            ))
+            .id();
+        app.update();
+        assert_eq!(app.world().get::<Boss>(boss).unwrap().current, Some(0));
+
+        app.world_mut().get_mut::<Boss>(boss).unwrap().current = Some(2);
+        app.update();
+        assert_eq!(app.world().get::<Boss>(boss).unwrap().current, Some(2));
+    }
+}
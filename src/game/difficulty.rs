@@ -0,0 +1,232 @@
+use bevy::{
+    app::{App, Update},
+    hierarchy::Parent,
+    prelude::{
+        in_state, Added, Commands, IntoSystemConfigs, Query, Res, ResMut, Resource, Without,
+    },
+    reflect::Reflect,
+    time::Time,
+};
+use bevy_stats::{Resource as StatResource, Stat};
+use strum_macros::EnumIter;
+
+use super::{
+    director::EncounterDirector,
+    modifiers::add_stat_mod,
+    run::Run,
+    stats::{Damage, Health, MoveSpeed, ResourceMax},
+};
+use crate::{
+    states::{AppState, GameState, TimerState},
+    twin_stick::{
+        actors::{Actor, Faction, PLAYER_FACTION},
+        player::Player,
+        weapons::Weapon,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, EnumIter)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+/// How far into the run the player is.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Progress {
+    pub wave: usize,
+    pub depth: usize,
+    pub minutes: f32,
+}
+
+/// A multiplier that grows linearly with each kind of progress.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct Curve {
+    pub base: f32,
+    pub per_wave: f32,
+    pub per_depth: f32,
+    pub per_minute: f32,
+}
+
+impl Curve {
+    pub const fn new(base: f32, per_wave: f32, per_depth: f32, per_minute: f32) -> Self {
+        Self {
+            base,
+            per_wave,
+            per_depth,
+            per_minute,
+        }
+    }
+
+    pub fn at(&self, progress: Progress) -> f32 {
+        self.base
+            + self.per_wave * progress.wave as f32
+            + self.per_depth * progress.depth as f32
+            + self.per_minute * progress.minutes
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct DifficultyCurve {
+    pub health: Curve,
+    pub damage: Curve,
+    pub move_speed: Curve,
+    pub spawn_count: Curve,
+}
+
+/// The selected difficulty and the curve behind each setting. Enemy stats are scaled with
+/// `bevy_stats` multipliers, so their base values are never touched.
+#[derive(Resource, Clone, Debug, PartialEq, Reflect)]
+pub struct DifficultySettings {
+    pub selected: Difficulty,
+    pub easy: DifficultyCurve,
+    pub normal: DifficultyCurve,
+    pub hard: DifficultyCurve,
+}
+
+impl Default for DifficultySettings {
+    fn default() -> Self {
+        Self {
+            selected: Difficulty::default(),
+            easy: DifficultyCurve {
+                health: Curve::new(0.75, 0.05, 0.05, 0.01),
+                damage: Curve::new(0.6, 0.03, 0.05, 0.),
+                move_speed: Curve::new(0.9, 0.01, 0.02, 0.),
+                spawn_count: Curve::new(0.75, 0.05, 0.05, 0.),
+            },
+            normal: DifficultyCurve {
+                health: Curve::new(1., 0.1, 0.1, 0.02),
+                damage: Curve::new(1., 0.05, 0.1, 0.01),
+                move_speed: Curve::new(1., 0.02, 0.03, 0.),
+                spawn_count: Curve::new(1., 0.1, 0.1, 0.02),
+            },
+            hard: DifficultyCurve {
+                health: Curve::new(1.3, 0.15, 0.15, 0.04),
+                damage: Curve::new(1.3, 0.08, 0.15, 0.02),
+                move_speed: Curve::new(1.1, 0.03, 0.04, 0.005),
+                spawn_count: Curve::new(1.3, 0.15, 0.15, 0.04),
+            },
+        }
+    }
+}
+
+impl DifficultySettings {
+    pub fn curve(&self) -> &DifficultyCurve {
+        match self.selected {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+pub fn progress(run: &Run, director: Option<&EncounterDirector>) -> Progress {
+    Progress {
+        wave: director.map(|w| w.next.saturating_sub(1)).unwrap_or(0),
+        depth: run.depth(),
+        minutes: run.elapsed.as_secs_f32() / 60.,
+    }
+}
+
+pub(super) fn difficulty_plugin(app: &mut App) {
+    app.init_resource::<DifficultySettings>()
+        .register_type::<DifficultySettings>();
+    app.add_systems(
+        Update,
+        (
+            tick_run_clock.run_if(in_state(TimerState::Playing)),
+            scale_new_enemies,
+            scale_new_enemy_weapons,
+        )
+            .run_if(in_state(GameState::InLevel))
+            .run_if(in_state(AppState::Game)),
+    );
+}
+
+pub fn tick_run_clock(mut run: ResMut<Run>, time: Res<Time>) {
+    run.elapsed += time.delta();
+}
+
+pub fn scale_new_enemies(
+    mut enemies: Query<
+        (
+            &Faction,
+            Option<&mut StatResource<Health>>,
+            Option<&mut ResourceMax<Health>>,
+            Option<&mut Stat<Damage>>,
+            Option<&mut Stat<MoveSpeed>>,
+        ),
+        (Added<Actor>, Without<Player>),
+    >,
+    settings: Res<DifficultySettings>,
+    run: Res<Run>,
+    director: Option<Res<EncounterDirector>>,
+    mut commands: Commands,
+) {
+    let curve = settings.curve();
+    let progress = progress(&run, director.as_deref());
+    for (faction, health, max, damage, speed) in enemies.iter_mut() {
+        if faction.0 == PLAYER_FACTION {
+            continue;
+        }
+        if let Some(mut health) = health {
+            let multiplier = curve.health.at(progress);
+            if let Some(id) = add_stat_mod::<Health>(&mut commands, multiplier) {
+                health.add_mod(id);
+                if let Some(mut max) = max {
+                    max.value *= multiplier;
+                }
+            }
+        }
+        if let Some(mut damage) = damage {
+            if let Some(id) = add_stat_mod::<Damage>(&mut commands, curve.damage.at(progress)) {
+                damage.add_mod(id);
+            }
+        }
+        if let Some(mut speed) = speed {
+            if let Some(id) =
+                add_stat_mod::<MoveSpeed>(&mut commands, curve.move_speed.at(progress))
+            {
+                speed.add_mod(id);
+            }
+        }
+    }
+}
+
+/// Enemy guns carry their own `Damage`, so they are scaled alongside their wielder.
+pub fn scale_new_enemy_weapons(
+    mut weapons: Query<(&Parent, &mut Stat<Damage>), Added<Weapon>>,
+    factions: Query<&Faction, Without<Player>>,
+    settings: Res<DifficultySettings>,
+    run: Res<Run>,
+    director: Option<Res<EncounterDirector>>,
+    mut commands: Commands,
+) {
+    let multiplier = settings
+        .curve()
+        .damage
+        .at(progress(&run, director.as_deref()));
+    for (parent, mut damage) in weapons.iter_mut() {
+        if !factions
+            .get(parent.get())
+            .is_ok_and(|w| w.0 != PLAYER_FACTION)
+        {
+            continue;
+        }
+        if let Some(id) = add_stat_mod::<Damage>(&mut commands, multiplier) {
+            damage.add_mod(id);
+        }
+    }
+}
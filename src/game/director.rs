@@ -0,0 +1,249 @@
+use avian2d::prelude::CollisionStarted;
+use bevy::{
+    app::{App, Update},
+    color::Alpha,
+    math::{Rect, Vec2},
+    prelude::{
+        in_state, resource_exists, Camera, Commands, Component, Entity, Event, EventReader,
+        EventWriter, GlobalTransform, IntoSystemConfigs, NextState, Query, Res, ResMut, Resource,
+        With, Without,
+    },
+    sprite::Sprite,
+    time::{Time, Timer, TimerMode},
+};
+use bevy_composable::{app_impl::ComplexSpawnable, tree::ComponentTree};
+use bevy_turborand::{DelegatedRng, GlobalRng};
+
+use super::{
+    difficulty::{progress, DifficultySettings, Progress},
+    run::Run,
+};
+use crate::{
+    action_system::{
+        actions::{oneshot::OneShotAction, spawn::SpawnAction, telegraphed},
+        prefabs::spawn_delay,
+    },
+    states::{AppState, GameState, TimerState},
+    twin_stick::{
+        actors::{Actor, Faction, PLAYER_FACTION},
+        player::{Player, TwinStickCamera},
+        utils::pos,
+    },
+};
+
+/// `count` copies of `enemy`, spawned `delay` seconds apart.
+#[derive(Clone)]
+pub struct WaveEntry {
+    pub enemy: ComponentTree,
+    pub count: u32,
+    pub delay: f32,
+}
+
+impl WaveEntry {
+    pub fn new(enemy: ComponentTree, count: u32, delay: f32) -> Self {
+        Self {
+            enemy,
+            count,
+            delay,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Wave {
+    pub entries: Vec<WaveEntry>,
+}
+
+impl Wave {
+    pub fn new(entries: Vec<WaveEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EncounterSpawnPoint {
+    pub position: Vec2,
+    pub difficulty: u32,
+}
+
+/// Runs the waves of the current arena. A wave starts once the previous one is dead and every
+/// spawner has fired, after a short breather.
+#[derive(Resource, Clone)]
+pub struct EncounterDirector {
+    pub waves: Vec<Wave>,
+    pub spawn_points: Vec<EncounterSpawnPoint>,
+    /// The wave that is currently being fought, if any.
+    pub current: Option<usize>,
+    pub next: usize,
+    pub living: usize,
+    pub intermission: Timer,
+    pub cleared: bool,
+}
+
+impl EncounterDirector {
+    pub fn new(waves: Vec<Wave>, spawn_points: Vec<EncounterSpawnPoint>) -> Self {
+        Self {
+            waves,
+            spawn_points,
+            current: None,
+            next: 0,
+            living: 0,
+            intermission: Timer::from_seconds(2., TimerMode::Once),
+            cleared: false,
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaveCleared {
+    pub wave: usize,
+}
+
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaCleared;
+
+/// The way out of an arena. It stays shut until the arena is cleared.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaExit {
+    pub locked: bool,
+}
+
+pub(super) fn director_plugin(app: &mut App) {
+    app.add_event::<WaveCleared>().add_event::<ArenaCleared>();
+    app.add_systems(
+        Update,
+        (
+            direct_encounter
+                .run_if(in_state(TimerState::Playing))
+                .run_if(resource_exists::<EncounterDirector>),
+            unlock_exits,
+            leave_through_exit,
+        )
+            .chain()
+            .run_if(in_state(GameState::InLevel))
+            .run_if(in_state(AppState::Game)),
+    );
+}
+
+/// The part of the world the camera can currently see.
+fn visible_area(
+    cameras: &Query<(&Camera, &GlobalTransform), With<TwinStickCamera>>,
+) -> Option<Rect> {
+    let (camera, transform) = cameras.get_single().ok()?;
+    let size = camera.logical_viewport_size()?;
+    let a = camera.viewport_to_world_2d(transform, Vec2::ZERO).ok()?;
+    let b = camera.viewport_to_world_2d(transform, size).ok()?;
+    Some(Rect::from_corners(a, b))
+}
+
+pub fn direct_encounter(
+    mut director: ResMut<EncounterDirector>,
+    enemies: Query<&Faction, (With<Actor>, Without<Player>)>,
+    // Guns and deployers keep their SpawnAction for good, so only count the one-shot spawners
+    // that haven't gone off yet.
+    spawners: Query<Entity, (With<SpawnAction>, With<OneShotAction>)>,
+    cameras: Query<(&Camera, &GlobalTransform), With<TwinStickCamera>>,
+    time: Res<Time>,
+    run: Res<Run>,
+    difficulty: Res<DifficultySettings>,
+    mut rng: ResMut<GlobalRng>,
+    mut wave_cleared: EventWriter<WaveCleared>,
+    mut arena_cleared: EventWriter<ArenaCleared>,
+    mut commands: Commands,
+) {
+    director.living = enemies.iter().filter(|w| w.0 != PLAYER_FACTION).count();
+    if director.cleared || director.living > 0 || !spawners.is_empty() {
+        return;
+    }
+    if let Some(wave) = director.current.take() {
+        wave_cleared.send(WaveCleared { wave });
+        director.intermission.reset();
+    }
+    if director.next >= director.waves.len() {
+        director.cleared = true;
+        arena_cleared.send(ArenaCleared);
+        return;
+    }
+    if !director.intermission.tick(time.delta()).finished() {
+        return;
+    }
+
+    let wave = director.next;
+    // Enemies come in out of sight, from the spawn points this wave is tough enough for.
+    let visible = visible_area(&cameras);
+    let hidden: Vec<&EncounterSpawnPoint> = director
+        .spawn_points
+        .iter()
+        .filter(|w| !visible.is_some_and(|area| area.contains(w.position)))
+        .collect();
+    let pool: Vec<&EncounterSpawnPoint> = if hidden.is_empty() {
+        director.spawn_points.iter().collect()
+    } else {
+        hidden
+    };
+    let eligible: Vec<&EncounterSpawnPoint> = pool
+        .iter()
+        .copied()
+        .filter(|w| w.difficulty as usize <= wave + 1)
+        .collect();
+    let points = if eligible.is_empty() { pool } else { eligible };
+
+    let count_scale = difficulty.curve().spawn_count.at(Progress {
+        wave,
+        ..progress(&run, Some(&*director))
+    });
+    if !points.is_empty() {
+        for entry in director.waves[wave].entries.iter() {
+            let count = (entry.count as f32 * count_scale).round() as u32;
+            for i in 0..count {
+                let point = points[rng.usize(0..points.len())];
+                commands.compose(
+                    pos(point.position.x, point.position.y)
+                        + spawn_delay(0.5 + entry.delay * i as f32, entry.enemy.clone())
+                        + telegraphed(),
+                );
+            }
+        }
+    }
+    director.current = Some(wave);
+    director.next += 1;
+}
+
+pub fn unlock_exits(
+    mut cleared: EventReader<ArenaCleared>,
+    mut exits: Query<(&mut ArenaExit, &mut Sprite)>,
+) {
+    if cleared.read().count() == 0 {
+        return;
+    }
+    for (mut exit, mut sprite) in exits.iter_mut() {
+        exit.locked = false;
+        sprite.color = sprite.color.with_alpha(1.);
+    }
+}
+
+pub fn leave_through_exit(
+    mut collisions: EventReader<CollisionStarted>,
+    exits: Query<&ArenaExit>,
+    players: Query<(), With<Player>>,
+    mut run: ResMut<Run>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for CollisionStarted(a, b) in collisions.read() {
+        let exit = exits.get(*a).or_else(|_| exits.get(*b));
+        let player = players.contains(*a) || players.contains(*b);
+        if !player || !exit.is_ok_and(|w| !w.locked) {
+            continue;
+        }
+        let Some(current) = run.current else {
+            return;
+        };
+        run.nodes[current].cleared = true;
+        if run.is_finished() {
+            app_state.set(AppState::MainMenu);
+        }
+        game_state.set(GameState::OverMap);
+        return;
+    }
+}
@@ -0,0 +1,202 @@
+use bevy::{
+    app::{App, Update},
+    log::warn,
+    prelude::{
+        BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, Event, EventReader,
+        EventWriter, IntoSystemConfigs, Name, Query, RemovedComponents,
+    },
+};
+use bevy_composable::{
+    app_impl::{ComplexSpawnable, ComponentTreeable},
+    tree::ComponentTree,
+};
+
+use crate::{
+    action_system::{actuator::ActuatorCondition, triggers::key_action::PlayerActionTrigger},
+    twin_stick::ai::keyboard::PlayerAction,
+};
+
+pub const EQUIPMENT_SLOTS: usize = PlayerAction::SHOOT.len();
+
+/// The weapons an actor is holding, one per fire button.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Equipment {
+    pub slots: [Option<Entity>; EQUIPMENT_SLOTS],
+}
+
+/// Marks a weapon entity as held in `slot` of its parent's `Equipment`.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Equipped {
+    pub slot: usize,
+}
+
+/// The tree a held weapon was built from, so it can be re-spawned after it is dropped.
+#[derive(Component, Clone)]
+pub struct EquipmentItem(pub ComponentTree);
+
+/// A slot number past the end of `Equipment::slots`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidSlot(pub usize);
+
+impl std::fmt::Display for InvalidSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "there is no equipment slot {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidSlot {}
+
+#[derive(Event, Clone)]
+pub enum EquipmentEvent {
+    Equip {
+        owner: Entity,
+        slot: usize,
+        weapon: ComponentTree,
+    },
+    Swap {
+        owner: Entity,
+        from: usize,
+        to: usize,
+    },
+    Drop {
+        owner: Entity,
+        slot: usize,
+    },
+}
+
+#[derive(Event, Clone)]
+pub struct WeaponDropped {
+    pub owner: Entity,
+    pub name: String,
+    pub item: EquipmentItem,
+}
+
+impl Equipment {
+    /// Spawn `weapon` as a child of `owner` in `slot`, returning whatever was held there before.
+    pub fn equip(
+        &mut self,
+        commands: &mut Commands,
+        owner: Entity,
+        slot: usize,
+        weapon: ComponentTree,
+    ) -> Result<Option<Entity>, InvalidSlot> {
+        let trigger = slot_trigger(slot).ok_or(InvalidSlot(slot))?;
+        let held = self.slots.get_mut(slot).ok_or(InvalidSlot(slot))?;
+        let previous = held.take();
+        let weapon_id = commands
+            .compose(weapon.clone() + (trigger, Equipped { slot }, EquipmentItem(weapon)).store());
+        commands.entity(owner).add_child(weapon_id);
+        *held = Some(weapon_id);
+        Ok(previous)
+    }
+
+    pub fn swap(
+        &mut self,
+        commands: &mut Commands,
+        from: usize,
+        to: usize,
+    ) -> Result<(), InvalidSlot> {
+        for slot in [from, to] {
+            if slot >= self.slots.len() {
+                return Err(InvalidSlot(slot));
+            }
+        }
+        self.slots.swap(from, to);
+        for slot in [from, to] {
+            if let (Some(weapon), Some(trigger)) = (self.slots[slot], slot_trigger(slot)) {
+                commands
+                    .entity(weapon)
+                    .remove::<ActuatorCondition>()
+                    .insert((trigger, Equipped { slot }));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn unequip(&mut self, slot: usize) -> Option<Entity> {
+        self.slots.get_mut(slot)?.take()
+    }
+
+    pub fn first_free(&self) -> Option<usize> {
+        self.slots.iter().position(|w| w.is_none())
+    }
+}
+
+/// The trigger for the fire button of `slot`, if there is one.
+pub fn slot_trigger(slot: usize) -> Option<PlayerActionTrigger> {
+    PlayerAction::shoot(slot).map(|w| PlayerActionTrigger::new([w]))
+}
+
+pub(super) fn equipment_plugin(app: &mut App) {
+    app.add_event::<EquipmentEvent>()
+        .add_event::<WeaponDropped>();
+
+    app.add_systems(
+        Update,
+        (handle_equipment_events, forget_despawned_weapons).chain(),
+    );
+}
+
+pub fn handle_equipment_events(
+    mut events: EventReader<EquipmentEvent>,
+    mut owners: Query<&mut Equipment>,
+    items: Query<(&EquipmentItem, Option<&Name>)>,
+    mut dropped: EventWriter<WeaponDropped>,
+    mut commands: Commands,
+) {
+    let mut drop_weapon = |commands: &mut Commands, owner: Entity, weapon: Entity| {
+        if let Ok((item, name)) = items.get(weapon) {
+            dropped.send(WeaponDropped {
+                owner,
+                name: name.map(|w| w.to_string()).unwrap_or_default(),
+                item: item.clone(),
+            });
+        }
+        commands.entity(weapon).despawn_recursive();
+    };
+
+    for event in events.read() {
+        match event {
+            EquipmentEvent::Equip {
+                owner,
+                slot,
+                weapon,
+            } => {
+                if let Ok(mut equipment) = owners.get_mut(*owner) {
+                    match equipment.equip(&mut commands, *owner, *slot, weapon.clone()) {
+                        Ok(Some(previous)) => drop_weapon(&mut commands, *owner, previous),
+                        Ok(None) => (),
+                        Err(e) => warn!("Could not equip weapon: {}", e),
+                    }
+                }
+            }
+            EquipmentEvent::Swap { owner, from, to } => {
+                if let Ok(mut equipment) = owners.get_mut(*owner) {
+                    if let Err(e) = equipment.swap(&mut commands, *from, *to) {
+                        warn!("Could not swap weapons: {}", e);
+                    }
+                }
+            }
+            EquipmentEvent::Drop { owner, slot } => {
+                if let Ok(mut equipment) = owners.get_mut(*owner) {
+                    if let Some(weapon) = equipment.unequip(*slot) {
+                        drop_weapon(&mut commands, *owner, weapon);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn forget_despawned_weapons(
+    mut removed: RemovedComponents<Equipped>,
+    mut owners: Query<&mut Equipment>,
+) {
+    for weapon in removed.read() {
+        for mut equipment in owners.iter_mut() {
+            if let Some(slot) = equipment.slots.iter().position(|w| *w == Some(weapon)) {
+                equipment.slots[slot] = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use avian2d::prelude::{Collider, CollisionLayers, CollisionStarted, RigidBody, Sensor};
+use bevy::{
+    app::{App, Update},
+    color::Color,
+    math::Vec2,
+    prelude::{
+        BuildChildren, Commands, Component, DespawnRecursiveExt, Entity, EventReader,
+        IntoSystemConfigs, Query,
+    },
+    render::view::Visibility,
+    sprite::Sprite,
+    utils::default,
+};
+use bevy_composable::{
+    app_impl::{ComplexSpawnable, ComponentTreeable},
+    tree::ComponentTree,
+    wrappers::name,
+};
+
+use super::equipment::WeaponDropped;
+use crate::twin_stick::physics::GamePhysicsLayer as GPL;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ItemKind {
+    Weapon,
+    Modifier,
+    Consumable,
+}
+
+/// Something that can sit in an inventory. `tree` is what gets spawned when the item is used:
+/// the weapon itself, the modifier entity, or the consumable's effect.
+#[derive(Clone)]
+pub struct InventoryItem {
+    pub name: String,
+    pub kind: ItemKind,
+    pub tree: ComponentTree,
+}
+
+impl InventoryItem {
+    pub fn new(name: impl Into<String>, kind: ItemKind, tree: ComponentTree) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            tree,
+        }
+    }
+}
+
+#[derive(Component, Clone, Default)]
+pub struct Inventory {
+    pub items: Vec<InventoryItem>,
+}
+
+impl Inventory {
+    pub fn take(&mut self, index: usize) -> Option<InventoryItem> {
+        (index < self.items.len()).then(|| self.items.remove(index))
+    }
+}
+
+/// A world object that moves its item into the inventory of whoever touches it.
+#[derive(Component, Clone)]
+pub struct Pickup(pub InventoryItem);
+
+pub fn pickup(item: InventoryItem, color: Color) -> ComponentTree {
+    let label = format!("Pickup ({})", item.name);
+    (
+        Pickup(item),
+        Sprite {
+            color,
+            custom_size: Some(Vec2::new(20., 20.)),
+            ..default()
+        },
+        Visibility::Visible,
+        RigidBody::Static,
+        Collider::circle(20.),
+        Sensor,
+        CollisionLayers::new(GPL::Trigger, [GPL::Player]),
+    )
+        .store()
+        + name(label)
+}
+
+/// Spawn a used consumable's effect as a child of whoever used it.
+pub fn use_consumable(commands: &mut Commands, user: Entity, item: &InventoryItem) {
+    let effect = commands.compose(item.tree.clone());
+    commands.entity(user).add_child(effect);
+}
+
+pub(super) fn inventory_plugin(app: &mut App) {
+    app.add_systems(Update, (collect_pickups, stash_dropped_weapons).chain());
+}
+
+pub fn collect_pickups(
+    mut collisions: EventReader<CollisionStarted>,
+    pickups: Query<&Pickup>,
+    mut inventories: Query<&mut Inventory>,
+    mut commands: Commands,
+) {
+    let mut collected = Vec::new();
+    for CollisionStarted(e1, e2) in collisions.read() {
+        let (pickup_id, collector) = match (pickups.get(*e1), pickups.get(*e2)) {
+            (Ok(_), _) => (*e1, *e2),
+            (Err(_), Ok(_)) => (*e2, *e1),
+            (Err(_), Err(_)) => continue,
+        };
+        if collected.contains(&pickup_id) {
+            continue;
+        }
+        if let (Ok(Pickup(item)), Ok(mut inventory)) =
+            (pickups.get(pickup_id), inventories.get_mut(collector))
+        {
+            inventory.items.push(item.clone());
+            collected.push(pickup_id);
+            commands.entity(pickup_id).despawn_recursive();
+        }
+    }
+}
+
+pub fn stash_dropped_weapons(
+    mut dropped: EventReader<WeaponDropped>,
+    mut inventories: Query<&mut Inventory>,
+) {
+    for WeaponDropped { owner, name, item } in dropped.read() {
+        if let Ok(mut inventory) = inventories.get_mut(*owner) {
+            inventory.items.push(InventoryItem::new(
+                name.clone(),
+                ItemKind::Weapon,
+                item.0.clone(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,283 @@
+use bevy::{
+    app::{App, Startup, Update},
+    asset::AssetApp,
+    color::Color,
+    ecs::system::{Commands, Res},
+    log::warn,
+    prelude::OnEnter,
+};
+use bevy_composable::{
+    app_impl::{ComplexSpawnable, ComponentTreeable},
+    tree::ComponentTree,
+};
+use bevy_stats::{Resource as StatResource, Stat};
+use std::collections::HashMap;
+
+use super::{
+    director::{EncounterDirector, EncounterSpawnPoint, Wave},
+    inventory::{pickup, InventoryItem, ItemKind},
+    run::{NodeKind, Run},
+    stats::{Damage, Health, MoveSpeed},
+};
+use crate::{
+    action_system::{
+        actions::telegraphed,
+        prefabs::{spawn_delay, spawn_prox_many},
+    },
+    arena::{
+        arena_objects::arena_exit,
+        generate_arena,
+        generator::{distances, furthest_cell, Cell},
+        import::{
+            load_level_folder, update_level_library, ImportedLevel, LevelEntity, LevelFile,
+            LevelLibrary, LevelLoader,
+        },
+        props::PropKind,
+        spawn_arena_from_map, Arena, ArenaGenConfig, PlayerStart,
+    },
+    content::{
+        bosses::warden,
+        deployables::sentry_kit,
+        enemies::{brood_mother, charger, healer, shotgunner, stumbler, turret},
+        modifiers::{aoe, dubler, faster},
+        waves::waves_for,
+        weapons::{cleaver, lance, railgun, wristgun},
+    },
+    states::GameState,
+    twin_stick::{actors::PLAYER_FACTION, player::Cursor, utils::pos},
+};
+
+pub const ARENA_RESOLUTION: f32 = 64.;
+
+pub(super) fn level_plugin(app: &mut App) {
+    app.init_resource::<ArenaGenConfig>()
+        .register_type::<ArenaGenConfig>();
+    app.init_asset::<LevelFile>()
+        .init_asset_loader::<LevelLoader>()
+        .init_resource::<LevelLibrary>();
+    app.add_systems(Startup, load_level_folder);
+    app.add_systems(Update, update_level_library);
+
+    app.add_systems(OnEnter(GameState::InLevel), load_level);
+}
+
+/// Add the stats named by a level entity's custom fields on top of its prefab.
+fn with_stat_fields(tree: ComponentTree, fields: &HashMap<String, f32>) -> ComponentTree {
+    fields
+        .iter()
+        .filter_map(|(field, value)| match field.as_str() {
+            "Health" => Some(StatResource::<Health>::new(*value).store()),
+            "MoveSpeed" => Some(Stat::<MoveSpeed>::new(*value).store()),
+            "Damage" => Some(Stat::<Damage>::new(*value).store()),
+            _ => None,
+        })
+        .fold(tree, |tree, stat| tree + stat)
+}
+
+fn spawner(count: u32, radius: f32, enemy: ComponentTree) -> ComponentTree {
+    spawn_prox_many(
+        1 << PLAYER_FACTION,
+        radius,
+        (0..count).map(move |i| spawn_delay(1.0 + 0.5 * i as f32, enemy.clone()) + telegraphed()),
+    ) + telegraphed()
+}
+
+fn weapon_pickup(label: &str, weapon: ComponentTree) -> ComponentTree {
+    pickup(
+        InventoryItem::new(label, ItemKind::Weapon, weapon),
+        Color::srgb(0.8, 0.6, 0.2),
+    )
+}
+
+fn modifier_pickup(label: &str, modifier: ComponentTree) -> ComponentTree {
+    pickup(
+        InventoryItem::new(label, ItemKind::Modifier, modifier),
+        Color::srgb(0.3, 0.7, 0.9),
+    )
+}
+
+/// Turn a designer-placed entity into its content prefab.
+fn spawn_level_entity(
+    commands: &mut Commands,
+    level: &Arena,
+    entity: &LevelEntity,
+    cursor: &Res<Cursor>,
+) {
+    let at = level.cell_to_world(entity.cell);
+    let field = |name: &str| entity.fields.get(name).copied();
+    if let Some(kind) = PropKind::from_identifier(&entity.identifier) {
+        commands.compose(kind.prefab(at.x, at.y));
+        return;
+    }
+    let tree = match entity.identifier.as_str() {
+        // Already handled by `load_imported_level`.
+        "Player" | "PlayerStart" | "EnemySpawn" => return,
+        "Stumbler" => with_stat_fields(stumbler(), &entity.fields),
+        "Turret" => with_stat_fields(turret(), &entity.fields),
+        "Shotgunner" => with_stat_fields(shotgunner(), &entity.fields),
+        "BroodMother" => with_stat_fields(brood_mother(), &entity.fields),
+        "Healer" => with_stat_fields(healer(), &entity.fields),
+        "Charger" => with_stat_fields(charger(), &entity.fields),
+        "Warden" => with_stat_fields(warden(), &entity.fields),
+        "Spawner" | "SpawnProx" => spawner(
+            field("Count").unwrap_or(1.) as u32,
+            field("Radius").unwrap_or(200.),
+            with_stat_fields(stumbler(), &entity.fields),
+        ),
+        "Exit" => {
+            commands.compose(arena_exit(at.x, at.y, level.resolution));
+            return;
+        }
+        "WristGun" => weapon_pickup("Wrist Gun", wristgun(cursor)),
+        "Cleaver" => weapon_pickup("Cleaver", cleaver(cursor)),
+        "Railgun" => weapon_pickup("Railgun", railgun(cursor)),
+        "Lance" => weapon_pickup("Lance", lance(cursor)),
+        "Sentry" => weapon_pickup("Sentry", sentry_kit(cursor)),
+        "Faster" => modifier_pickup("Faster", faster()),
+        "AoE" => modifier_pickup("AoE", aoe()),
+        "Dubler" => modifier_pickup("Dubler", dubler()),
+        other => {
+            warn!("No prefab for level entity {}", other);
+            return;
+        }
+    };
+    commands.compose(pos(at.x, at.y) + tree);
+}
+
+fn load_level(
+    mut commands: Commands,
+    cursor: Res<Cursor>,
+    run: Res<Run>,
+    config: Res<ArenaGenConfig>,
+    library: Res<LevelLibrary>,
+) {
+    let Some(node) = run.current_node() else {
+        return;
+    };
+    // Hand-made levels tagged with the node kind take priority over generated ones.
+    let handmade: Vec<&ImportedLevel> = library.tagged(node.kind.label()).collect();
+    if !handmade.is_empty() {
+        load_imported_level(
+            &mut commands,
+            &cursor,
+            handmade[(node.seed % handmade.len() as u64) as usize],
+            waves_for(node.kind, node.layer),
+        );
+        return;
+    }
+
+    let generated = generate_arena(node.seed, &config);
+    let level = Arena {
+        arena_map: generated.map.clone(),
+        resolution: ARENA_RESOLUTION,
+    };
+    spawn_arena_from_map(&mut commands, &level);
+    commands.insert_resource(PlayerStart(level.cell_to_world(generated.player_start)));
+
+    // Pickups go a few steps away from the start so the player sees them straight away.
+    let nearby: Vec<Cell> = distances(&generated.map, generated.player_start)
+        .into_iter()
+        .filter(|(_, distance)| (3..=5).contains(distance))
+        .map(|w| w.0)
+        .collect();
+    let mut pickup_cells = nearby.iter().step_by((nearby.len() / 2).max(1));
+
+    if node.layer == 0 {
+        if let Some(cell) = pickup_cells.next() {
+            let at = level.cell_to_world(*cell);
+            let (label, weapon) = [
+                ("Wrist Gun", wristgun(&cursor)),
+                ("Cleaver", cleaver(&cursor)),
+                ("Railgun", railgun(&cursor)),
+                ("Lance", lance(&cursor)),
+                ("Sentry", sentry_kit(&cursor)),
+            ][(node.seed % 5) as usize]
+                .clone();
+            commands.compose(pos(at.x, at.y) + weapon_pickup(label, weapon));
+        }
+    }
+    if node.kind != NodeKind::Arena {
+        if let Some(cell) = pickup_cells.next() {
+            let (label, modifier) = [("Faster", faster()), ("AoE", aoe()), ("Dubler", dubler())]
+                [(node.seed % 3) as usize]
+                .clone();
+            let at = level.cell_to_world(*cell);
+            commands.compose(pos(at.x, at.y) + modifier_pickup(label, modifier));
+        }
+    }
+
+    for (cell, kind) in generated.props.iter() {
+        let at = level.cell_to_world(*cell);
+        commands.compose(kind.prefab(at.x, at.y));
+    }
+
+    let exit = level.cell_to_world(generated.exit);
+    commands.compose(arena_exit(exit.x, exit.y, ARENA_RESOLUTION));
+
+    commands.insert_resource(EncounterDirector::new(
+        waves_for(node.kind, node.layer),
+        generated
+            .spawners
+            .iter()
+            .map(|w| EncounterSpawnPoint {
+                position: level.cell_to_world(w.cell),
+                difficulty: w.difficulty,
+            })
+            .collect(),
+    ));
+}
+
+/// Designer-placed `EnemySpawn` points feed the director, with an optional `Difficulty` field.
+fn load_imported_level(
+    commands: &mut Commands,
+    cursor: &Res<Cursor>,
+    imported: &ImportedLevel,
+    waves: Vec<Wave>,
+) {
+    let level = Arena {
+        arena_map: imported.map.clone(),
+        resolution: ARENA_RESOLUTION,
+    };
+    spawn_arena_from_map(commands, &level);
+
+    // Fall back to the first open cell when the designer forgot to place the player.
+    let start = imported
+        .entities
+        .iter()
+        .find(|w| matches!(w.identifier.as_str(), "Player" | "PlayerStart"))
+        .map(|w| w.cell)
+        .or_else(|| {
+            imported
+                .map
+                .iter()
+                .enumerate()
+                .find_map(|(y, row)| row.iter().position(|w| w.is_walkable()).map(|x| (y, x)))
+        })
+        .unwrap_or_default();
+    commands.insert_resource(PlayerStart(level.cell_to_world(start)));
+
+    if !imported.entities.iter().any(|w| w.identifier == "Exit") {
+        let exit = level.cell_to_world(furthest_cell(&imported.map, start));
+        commands.compose(arena_exit(exit.x, exit.y, ARENA_RESOLUTION));
+    }
+
+    let spawn_points: Vec<EncounterSpawnPoint> = imported
+        .entities
+        .iter()
+        .filter(|w| w.identifier == "EnemySpawn")
+        .map(|w| EncounterSpawnPoint {
+            position: level.cell_to_world(w.cell),
+            difficulty: w.fields.get("Difficulty").copied().unwrap_or(1.) as u32,
+        })
+        .collect();
+    let waves = if spawn_points.is_empty() {
+        Vec::new()
+    } else {
+        waves
+    };
+    commands.insert_resource(EncounterDirector::new(waves, spawn_points));
+
+    for entity in imported.entities.iter() {
+        spawn_level_entity(commands, &level, entity, cursor);
+    }
+}
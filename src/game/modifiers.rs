@@ -0,0 +1,246 @@
+use bevy::{
+    app::{App, Update},
+    hierarchy::{Children, HierarchyQueryExt, Parent},
+    prelude::{
+        BuildChildren, Commands, Component, Entity, EventWriter, IntoSystemConfigs, OnRemove,
+        Query, Trigger, With, Without,
+    },
+};
+use bevy_composable::{app_impl::ComplexSpawnable, tree::ComponentTree};
+use bevy_stats::{
+    statmod::{ModType, StatModifier, StatValueChange},
+    DeleteStatMod, RPGStat, Stat,
+};
+use std::time::Duration;
+
+use super::{
+    inventory::{Inventory, InventoryItem},
+    stats::{Damage, ProjectileSpeed},
+};
+use crate::action_system::{
+    actions::spawn::{AngleOffset, Launch, SpawnAction},
+    actuator::Actuator,
+};
+
+/// How a modifier rewrites the payload of every launching `SpawnAction` on its weapon.
+#[derive(Clone)]
+pub enum PayloadPatch {
+    /// Fire `copies` of each projectile, fanned out `spread` radians apart.
+    Multiply { copies: usize, spread: f32 },
+    /// Add extra components to each projectile.
+    Append(ComponentTree),
+}
+
+/// An item attached as a child of a weapon. The multipliers and patches apply to the weapon and
+/// all of its descendants, and are reverted when this component goes away.
+#[derive(Component, Clone)]
+pub struct WeaponModifier {
+    pub damage: f32,
+    pub projectile_speed: f32,
+    pub cooldown: f32,
+    pub payload: Vec<PayloadPatch>,
+}
+
+impl Default for WeaponModifier {
+    fn default() -> Self {
+        Self {
+            damage: 1.,
+            projectile_speed: 1.,
+            cooldown: 1.,
+            payload: Vec::new(),
+        }
+    }
+}
+
+/// The stat modifier entities a `WeaponModifier` has spawned, so they can be deleted later.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ModifierEffects {
+    pub stat_mods: Vec<Entity>,
+}
+
+/// The inventory item a modifier was attached from.
+#[derive(Component, Clone)]
+pub struct AttachedItem(pub InventoryItem);
+
+/// Marks a weapon whose cooldowns and payloads need rebuilding from their bases.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ModifiersDirty;
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct BaseCooldown(pub Duration);
+
+#[derive(Component, Clone)]
+pub struct BasePayload(pub Vec<(ComponentTree, AngleOffset)>);
+
+pub fn attach_modifier(commands: &mut Commands, weapon: Entity, item: &InventoryItem) -> Entity {
+    let modifier = commands.compose(item.tree.clone());
+    commands
+        .entity(modifier)
+        .insert(AttachedItem(item.clone()))
+        .set_parent(weapon);
+    modifier
+}
+
+pub(super) fn modifiers_plugin(app: &mut App) {
+    app.add_observer(revert_weapon_modifier);
+    app.add_observer(return_detached_items);
+    app.add_systems(
+        Update,
+        (apply_weapon_modifiers, rebuild_dirty_weapons).chain(),
+    );
+}
+
+/// Spawn a multiplier for stat `T`, unless it would do nothing. The caller adds it to the stat.
+pub(super) fn add_stat_mod<T: RPGStat>(commands: &mut Commands, multiplier: f32) -> Option<Entity> {
+    if multiplier == 1. {
+        return None;
+    }
+    Some(
+        commands
+            .spawn((
+                StatValueChange::<T>::new(multiplier, ModType::Multiplier),
+                StatModifier,
+            ))
+            .id(),
+    )
+}
+
+pub fn apply_weapon_modifiers(
+    modifiers: Query<(Entity, &WeaponModifier, &Parent), Without<ModifierEffects>>,
+    children: Query<&Children>,
+    mut damages: Query<&mut Stat<Damage>>,
+    mut speeds: Query<&mut Stat<ProjectileSpeed>>,
+    mut commands: Commands,
+) {
+    for (modifier_id, modifier, weapon) in modifiers.iter() {
+        let weapon = weapon.get();
+        let mut effects = ModifierEffects::default();
+        for part in std::iter::once(weapon).chain(children.iter_descendants(weapon)) {
+            if let Ok(mut damage) = damages.get_mut(part) {
+                if let Some(id) = add_stat_mod::<Damage>(&mut commands, modifier.damage) {
+                    damage.add_mod(id);
+                    effects.stat_mods.push(id);
+                }
+            }
+            if let Ok(mut speed) = speeds.get_mut(part) {
+                if let Some(id) =
+                    add_stat_mod::<ProjectileSpeed>(&mut commands, modifier.projectile_speed)
+                {
+                    speed.add_mod(id);
+                    effects.stat_mods.push(id);
+                }
+            }
+        }
+        commands.entity(modifier_id).insert(effects);
+        commands.entity(weapon).insert(ModifiersDirty);
+    }
+}
+
+pub fn revert_weapon_modifier(
+    trigger: Trigger<OnRemove, WeaponModifier>,
+    modifiers: Query<(&ModifierEffects, &Parent)>,
+    mut deletions: EventWriter<DeleteStatMod>,
+    mut commands: Commands,
+) {
+    if let Ok((effects, weapon)) = modifiers.get(trigger.entity()) {
+        for stat_mod in effects.stat_mods.iter() {
+            deletions.send(DeleteStatMod(*stat_mod));
+        }
+        if let Some(mut weapon) = commands.get_entity(weapon.get()) {
+            weapon.try_insert(ModifiersDirty);
+        }
+    }
+}
+
+/// A modifier that leaves its weapon goes back into the inventory of whoever was holding it.
+pub fn return_detached_items(
+    trigger: Trigger<OnRemove, AttachedItem>,
+    items: Query<&AttachedItem>,
+    parents: Query<&Parent>,
+    mut inventories: Query<&mut Inventory>,
+) {
+    let entity = trigger.entity();
+    if let Ok(AttachedItem(item)) = items.get(entity) {
+        if let Some(mut inventory) = parents
+            .iter_ancestors(entity)
+            .find_map(|w| inventories.get_mut(w).ok())
+        {
+            inventory.items.push(item.clone());
+        }
+    }
+}
+
+pub fn rebuild_dirty_weapons(
+    weapons: Query<Entity, With<ModifiersDirty>>,
+    children: Query<&Children>,
+    modifiers: Query<&WeaponModifier>,
+    mut actuators: Query<(Entity, &mut Actuator, Option<&BaseCooldown>)>,
+    // Only launchers fire projectiles; deployers and other spawners keep their payload as is.
+    mut spawners: Query<(Entity, &mut SpawnAction, Option<&BasePayload>), With<Launch>>,
+    mut commands: Commands,
+) {
+    for weapon in weapons.iter() {
+        commands.entity(weapon).remove::<ModifiersDirty>();
+        let parts: Vec<Entity> = std::iter::once(weapon)
+            .chain(children.iter_descendants(weapon))
+            .collect();
+        let active: Vec<&WeaponModifier> = children
+            .get(weapon)
+            .map(|w| w.iter().filter_map(|c| modifiers.get(*c).ok()).collect())
+            .unwrap_or_default();
+
+        let cooldown_factor: f32 = active.iter().map(|w| w.cooldown).product();
+        for part in parts.iter() {
+            if let Ok((entity, mut actuator, base)) = actuators.get_mut(*part) {
+                let base = match base {
+                    Some(BaseCooldown(duration)) => *duration,
+                    None => {
+                        let duration = actuator.cooldown.duration();
+                        commands.entity(entity).insert(BaseCooldown(duration));
+                        duration
+                    }
+                };
+                actuator
+                    .cooldown
+                    .set_duration(base.mul_f32(cooldown_factor));
+            }
+
+            if let Ok((entity, mut spawner, base)) = spawners.get_mut(*part) {
+                let base = match base {
+                    Some(BasePayload(payload)) => payload.clone(),
+                    None => {
+                        let payload = spawner.payload.clone();
+                        commands.entity(entity).insert(BasePayload(payload.clone()));
+                        payload
+                    }
+                };
+                spawner.payload = active
+                    .iter()
+                    .flat_map(|w| w.payload.iter())
+                    .fold(base, patch_payload);
+            }
+        }
+    }
+}
+
+fn patch_payload(
+    payload: Vec<(ComponentTree, AngleOffset)>,
+    patch: &PayloadPatch,
+) -> Vec<(ComponentTree, AngleOffset)> {
+    match patch {
+        PayloadPatch::Multiply { copies, spread } => payload
+            .into_iter()
+            .flat_map(|(tree, angle)| {
+                let middle = (*copies as f32 - 1.) * 0.5;
+                (0..*copies).map(move |i| {
+                    let offset = angle.0.to_angle() + (i as f32 - middle) * spread;
+                    (tree.clone(), offset.into())
+                })
+            })
+            .collect(),
+        PayloadPatch::Append(extra) => payload
+            .into_iter()
+            .map(|(tree, angle)| (tree + extra.clone(), angle))
+            .collect(),
+    }
+}
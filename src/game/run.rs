@@ -0,0 +1,258 @@
+use bevy::{
+    app::{App, Update},
+    hierarchy::{Children, HierarchyQueryExt},
+    log::warn,
+    prelude::{
+        in_state, Added, Changed, Commands, DespawnRecursiveExt, Entity, EventWriter,
+        IntoSystemConfigs, NextState, OnEnter, OnExit, Query, Res, ResMut, Resource, With,
+    },
+};
+use bevy_stats::{
+    statmod::{ModType, StatValueChange},
+    Resource as StatResource, ResourceChangeEvent,
+};
+use bevy_turborand::{DelegatedRng, GlobalRng, RngComponent};
+use std::time::Duration;
+
+use super::{
+    equipment::{Equipment, EquipmentItem, EQUIPMENT_SLOTS},
+    inventory::Inventory,
+    modifiers::AttachedItem,
+    stats::Health,
+};
+use crate::{
+    states::{unload_world, AppState, GameState},
+    twin_stick::player::Player,
+};
+
+pub const RUN_LAYERS: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    Arena,
+    Elite,
+    Boss,
+}
+
+impl NodeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeKind::Arena => "Arena",
+            NodeKind::Elite => "Elite",
+            NodeKind::Boss => "Boss",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapNode {
+    pub layer: usize,
+    pub kind: NodeKind,
+    pub seed: u64,
+    pub next: Vec<usize>,
+    pub cleared: bool,
+}
+
+/// Everything about the player that survives between arenas.
+#[derive(Clone)]
+pub struct PlayerSnapshot {
+    pub health: f32,
+    pub inventory: Inventory,
+    pub weapons: [Option<EquipmentItem>; EQUIPMENT_SLOTS],
+}
+
+/// The current run: its seed, the branching map of arenas and where the player is on it.
+#[derive(Resource, Clone)]
+pub struct Run {
+    pub seed: u64,
+    pub nodes: Vec<MapNode>,
+    pub layers: Vec<Vec<usize>>,
+    pub current: Option<usize>,
+    pub player: Option<PlayerSnapshot>,
+    /// Time spent inside arenas so far.
+    pub elapsed: Duration,
+}
+
+impl Run {
+    pub fn new(seed: u64) -> Self {
+        let mut rng = RngComponent::with_seed(seed);
+        let mut nodes: Vec<MapNode> = Vec::new();
+        let mut layers: Vec<Vec<usize>> = Vec::new();
+
+        for layer in 0..RUN_LAYERS {
+            let width = if layer == RUN_LAYERS - 1 {
+                1
+            } else {
+                rng.usize(2..=4)
+            };
+            let ids = (0..width)
+                .map(|_| {
+                    let kind = if layer == RUN_LAYERS - 1 {
+                        NodeKind::Boss
+                    } else if layer > 0 && rng.f32() < 0.25 {
+                        NodeKind::Elite
+                    } else {
+                        NodeKind::Arena
+                    };
+                    nodes.push(MapNode {
+                        layer,
+                        kind,
+                        seed: rng.u64(..),
+                        next: Vec::new(),
+                        cleared: false,
+                    });
+                    nodes.len() - 1
+                })
+                .collect();
+            layers.push(ids);
+        }
+
+        for pair in layers.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            for (i, id) in from.iter().enumerate() {
+                let j = i * to.len() / from.len();
+                nodes[*id].next.push(to[j]);
+                if j + 1 < to.len() && rng.bool() {
+                    nodes[*id].next.push(to[j + 1]);
+                }
+            }
+            // Every node has to be reachable from somewhere in the layer before it.
+            for (j, id) in to.iter().enumerate() {
+                if !from.iter().any(|w| nodes[*w].next.contains(id)) {
+                    nodes[from[j * from.len() / to.len()]].next.push(*id);
+                }
+            }
+        }
+
+        Self {
+            seed,
+            nodes,
+            layers,
+            current: None,
+            player: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// The nodes the player may pick next.
+    pub fn available(&self) -> Vec<usize> {
+        match self.current {
+            Some(current) => self.nodes[current].next.clone(),
+            None => self.layers[0].clone(),
+        }
+    }
+
+    pub fn current_node(&self) -> Option<&MapNode> {
+        self.current.map(|w| &self.nodes[w])
+    }
+
+    pub fn depth(&self) -> usize {
+        self.current_node().map(|w| w.layer).unwrap_or(0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current_node()
+            .is_some_and(|w| w.cleared && w.next.is_empty())
+    }
+}
+
+pub(super) fn run_plugin(app: &mut App) {
+    app.add_systems(OnEnter(AppState::Game), start_run);
+    app.add_systems(
+        OnExit(GameState::InLevel),
+        (snapshot_player, unload_world).chain(),
+    );
+    app.add_systems(
+        Update,
+        (restore_player, end_run_on_player_death)
+            .run_if(in_state(GameState::InLevel))
+            .run_if(in_state(AppState::Game)),
+    );
+}
+
+pub fn start_run(
+    mut commands: Commands,
+    mut rng: ResMut<GlobalRng>,
+    mut state: ResMut<NextState<GameState>>,
+) {
+    commands.insert_resource(Run::new(rng.u64(..)));
+    state.set(GameState::OverMap);
+}
+
+pub fn snapshot_player(
+    mut run: ResMut<Run>,
+    players: Query<(&StatResource<Health>, &Inventory, &Equipment), With<Player>>,
+    items: Query<&EquipmentItem>,
+    children: Query<&Children>,
+    attached: Query<&AttachedItem>,
+) {
+    if let Ok((health, inventory, equipment)) = players.get_single() {
+        // Modifiers come off their weapons between arenas and wait in the inventory.
+        let mut inventory = inventory.clone();
+        inventory.items.extend(
+            equipment
+                .slots
+                .iter()
+                .flatten()
+                .flat_map(|w| children.iter_descendants(*w))
+                .filter_map(|w| attached.get(w).ok())
+                .map(|w| w.0.clone()),
+        );
+        run.player = Some(PlayerSnapshot {
+            health: health.current_value(),
+            inventory,
+            weapons: equipment
+                .slots
+                .map(|w| w.and_then(|w| items.get(w).ok().cloned())),
+        });
+    }
+}
+
+/// The player isn't despawned at zero health like other actors; their death ends the run instead.
+pub fn end_run_on_player_death(
+    players: Query<&StatResource<Health>, (With<Player>, Changed<StatResource<Health>>)>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if players.iter().any(|w| w.current_value() <= 0.) {
+        game_state.set(GameState::OverMap);
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+/// Put the snapshot taken at the end of the last arena back onto a freshly spawned player.
+pub fn restore_player(
+    run: Res<Run>,
+    mut players: Query<
+        (
+            Entity,
+            &StatResource<Health>,
+            &mut Inventory,
+            &mut Equipment,
+        ),
+        Added<Player>,
+    >,
+    mut health_changes: EventWriter<ResourceChangeEvent<Health>>,
+    mut commands: Commands,
+) {
+    let Some(snapshot) = &run.player else {
+        return;
+    };
+    for (player, health, mut inventory, mut equipment) in players.iter_mut() {
+        *inventory = snapshot.inventory.clone();
+        for (slot, item) in snapshot.weapons.iter().enumerate() {
+            if let Some(weapon) = equipment.unequip(slot) {
+                commands.entity(weapon).despawn_recursive();
+            }
+            if let Some(EquipmentItem(tree)) = item {
+                if let Err(e) = equipment.equip(&mut commands, player, slot, tree.clone()) {
+                    warn!("Could not restore weapon: {}", e);
+                }
+            }
+        }
+        health_changes.send(ResourceChangeEvent {
+            change: StatValueChange::new(snapshot.health - health.current_value(), ModType::Offset),
+            target: player,
+        });
+    }
+}
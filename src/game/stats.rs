@@ -1,7 +1,12 @@
-use bevy::{app::App, reflect::Reflect};
+use bevy::{
+    app::App,
+    prelude::{Commands, Component, OnInsert, Query, Trigger},
+    reflect::Reflect,
+};
 use bevy_stats::{
     RPGResource, RPGStat, Resource, ResourceChangeEvent, Stat, StatChangeEvent, StatRegisterable,
 };
+use std::marker::PhantomData;
 
 #[derive(Reflect, Clone, Copy, Debug, Hash)]
 pub struct Health;
@@ -46,6 +51,37 @@ impl RPGStat for Accuracy {}
 
 impl RPGStat for Damage {}
 
+/// What a `Resource<T>` holds when full: the value it was added with, scaled along with it by
+/// difficulty scaling. `Health` can overmax, so heals use this to know where to stop, and
+/// thresholds to tell how full a resource is.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ResourceMax<T: RPGResource> {
+    pub value: f32,
+    marker: PhantomData<T>,
+}
+
+impl<T: RPGResource> ResourceMax<T> {
+    pub fn new(value: f32) -> Self {
+        Self {
+            value,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Resources start out full, so whatever one is inserted with is its maximum.
+fn record_resource_max<T: RPGResource>(
+    trigger: Trigger<OnInsert, Resource<T>>,
+    resources: Query<&Resource<T>>,
+    mut commands: Commands,
+) {
+    if let Ok(resource) = resources.get(trigger.entity()) {
+        commands
+            .entity(trigger.entity())
+            .insert(ResourceMax::<T>::new(resource.current_value()));
+    }
+}
+
 pub(super) fn stats_plugin(app: &mut App) {
     app.register_stat::<MoveSpeed>()
         .register_stat::<Damage>()
@@ -54,4 +90,5 @@ pub(super) fn stats_plugin(app: &mut App) {
         .register_stat::<ProjectileSpeed>();
 
     app.register_resource::<Health>();
+    app.add_observer(record_resource_max::<Health>);
 }
@@ -0,0 +1,5 @@
+//! The std-only parts of the game, so benches can use them without pulling in the whole app.
+
+pub mod arena {
+    pub mod merge;
+}
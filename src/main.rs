@@ -1,6 +1,7 @@
 #![feature(trivial_bounds)]
 
 use action_system::ActionSystemPlugin;
+use arena::ArenaPlugin;
 use assets::AssetPlugin;
 use bevy::{
     app::App,
@@ -8,16 +9,19 @@ use bevy::{
     window::{Window, WindowPlugin},
     DefaultPlugins,
 };
+use controls::ControlsPlugin;
 use debug::DebugPlugin;
 use game::GamePlugin;
 use states::StatePlugin;
 use twin_stick::TwinStickPlugin;
+use ui::UiPlugin;
 use util::UtilPlugin;
 
 mod action_system;
 mod arena;
 mod assets;
 mod content;
+mod controls;
 mod debug;
 mod game;
 mod graphics;
@@ -46,13 +50,13 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     }));
 
     app.add_plugins(AssetPlugin);
+    app.add_plugins(ControlsPlugin);
     // app.add_plugins(StatPlugin);
     app.add_plugins(TwinStickPlugin);
     app.add_plugins(ActionSystemPlugin);
 
-    // app.add_plugins((StatePlugin, UiPlugin));
-    app.add_plugins(StatePlugin);
-    app.add_plugins(GamePlugin);
+    app.add_plugins((StatePlugin, UiPlugin));
+    app.add_plugins((GamePlugin, ArenaPlugin));
     app.add_plugins(DebugPlugin);
     app.add_plugins(UtilPlugin);
 
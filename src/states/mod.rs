@@ -1,6 +1,8 @@
-use avian2d::prelude::{Collider, LinearVelocity};
+use avian2d::prelude::{Collider, LinearVelocity, Physics, PhysicsTime};
 use bevy::{app::AppExit, prelude::*, reflect::Reflect};
 
+use crate::action_system::actuator::Actuator;
+
 pub struct StatePlugin;
 
 impl Plugin for StatePlugin {
@@ -15,18 +17,29 @@ impl Plugin for StatePlugin {
 
         // app.add_systems(AppState::MainMenu, main_menu_gui);
 
-        app.add_systems(Update, pause_on_esc.run_if(in_state(AppState::Game)));
+        // Any menu over the game pauses it, so moving between menus keeps it paused.
+        app.add_systems(OnExit(UIState::None), pause_timers);
+        app.add_systems(OnEnter(UIState::None), resume_timers);
+
+        app.add_systems(
+            Update,
+            (pause_on_esc, toggle_inventory).run_if(in_state(AppState::Game)),
+        );
     }
 }
 
-fn unload_world(
+pub(crate) fn unload_world(
     mut commands: Commands,
-    gameworld_entities: Query<Entity, Or<(With<LinearVelocity>, With<Collider>)>>,
+    gameworld_entities: Query<
+        Entity,
+        (
+            Or<(With<LinearVelocity>, With<Collider>, With<Actuator>)>,
+            Without<Parent>,
+        ),
+    >,
 ) {
     for actor in gameworld_entities.into_iter() {
-        let mut entity = commands.entity(actor);
-        entity.despawn_descendants();
-        entity.despawn();
+        commands.entity(actor).despawn_recursive();
     }
 }
 
@@ -65,13 +78,38 @@ pub enum UIState {
 
 pub(crate) fn pause_on_esc(
     input: Res<ButtonInput<KeyCode>>,
+    current: Res<State<UIState>>,
     mut state: ResMut<NextState<UIState>>,
 ) {
-    if input.pressed(KeyCode::Escape) {
+    if input.just_pressed(KeyCode::Escape) && *current.get() == UIState::None {
         state.set(UIState::Pause)
     }
 }
 
+pub(crate) fn toggle_inventory(
+    input: Res<ButtonInput<KeyCode>>,
+    current: Res<State<UIState>>,
+    mut state: ResMut<NextState<UIState>>,
+) {
+    if input.just_pressed(KeyCode::Tab) {
+        match current.get() {
+            UIState::None => state.set(UIState::Inventory),
+            UIState::Inventory => state.set(UIState::None),
+            _ => (),
+        }
+    }
+}
+
+fn pause_timers(mut state: ResMut<NextState<TimerState>>, mut physics: ResMut<Time<Physics>>) {
+    state.set(TimerState::Paused);
+    physics.pause();
+}
+
+fn resume_timers(mut state: ResMut<NextState<TimerState>>, mut physics: ResMut<Time<Physics>>) {
+    state.set(TimerState::Playing);
+    physics.unpause();
+}
+
 fn exit(mut app_exit_events: EventWriter<AppExit>) {
     app_exit_events.send(AppExit::Success);
 }
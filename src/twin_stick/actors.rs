@@ -7,10 +7,11 @@ use bevy::{
     math::{Quat, Vec3, Vec3Swizzles},
     prelude::{
         in_state, App, Bundle, Changed, Commands, Component, DespawnRecursiveExt, Entity,
-        GlobalTransform, InheritedVisibility, IntoSystemConfigs, Parent, Query, Transform, Update,
-        Vec2, Visibility, With, Without,
+        GlobalTransform, InheritedVisibility, IntoSystemConfigs, Parent, Query, Res, Transform,
+        Update, Vec2, Visibility, With, Without,
     },
     reflect::Reflect,
+    time::{Time, Timer, TimerMode},
 };
 use bevy_stats::{Resource, Stat};
 
@@ -47,6 +48,21 @@ impl Default for Actor {
 #[derive(Clone, Copy, PartialEq, Eq, Reflect, Debug, Component)]
 pub struct Tracking(pub Option<Entity>);
 
+/// Takes no damage while present, e.g. during a dash.
+#[derive(Clone, Copy, PartialEq, Eq, Reflect, Debug, Component)]
+pub struct Invulnerable;
+
+/// Knocked off balance until the timer runs out, which interrupts action sequences that can be
+/// cancelled.
+#[derive(Clone, PartialEq, Reflect, Debug, Component)]
+pub struct Stunned(pub Timer);
+
+impl Stunned {
+    pub fn new(secs: f32) -> Self {
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Reflect, Debug, Component)]
 pub struct Head;
 
@@ -72,11 +88,19 @@ pub(super) fn actor_plugin(app: &mut App) {
         .register_type::<Faction>()
         .register_type::<Legs>()
         .register_type::<Head>()
-        .register_type::<Tracking>();
+        .register_type::<Tracking>()
+        .register_type::<Invulnerable>()
+        .register_type::<Stunned>();
 
     app.add_systems(
         Update,
-        (facing_update_system, animate_legs, health_death).run_if(in_state(TimerState::Playing)),
+        (
+            facing_update_system,
+            animate_legs,
+            health_death,
+            recover_from_stun,
+        )
+            .run_if(in_state(TimerState::Playing)),
     );
 }
 
@@ -94,10 +118,11 @@ pub fn facing_update_system(
     for entity in todo_entities.iter() {
         let entity = entity.clone();
         let target = transforms.get(entity).unwrap().1.unwrap().0;
-        let direction: Vec2 = match target {
-            Some(target_entity) => (transforms.get(target_entity).unwrap().0.translation()
-                - transforms.get(entity).unwrap().0.translation())
-            .xy(),
+        // The target may have died since it was picked.
+        let direction: Vec2 = match target.and_then(|w| transforms.get(w).ok()) {
+            Some(target) => {
+                (target.0.translation() - transforms.get(entity).unwrap().0.translation()).xy()
+            }
             None => match parents.get(transforms.get(entity).unwrap().4.unwrap().get()) {
                 Ok(parent_vel) => parent_vel.0,
                 Err(_) => Vec2::X,
@@ -159,3 +184,15 @@ pub fn health_death(
         }
     }
 }
+
+pub fn recover_from_stun(
+    mut stunned: Query<(Entity, &mut Stunned)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut stun) in stunned.iter_mut() {
+        if stun.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
@@ -1,14 +1,11 @@
-use bevy::{
-    input::mouse::MouseButton,
-    prelude::{Component, KeyCode, Query, Reflect, With},
-};
+use bevy::prelude::{Component, Query, Reflect, With};
 use leafwing_input_manager::{
-    prelude::{ActionState, InputMap, VirtualDPad},
-    Actionlike, InputControlKind, InputManagerBundle,
+    prelude::ActionState, Actionlike, InputControlKind, InputManagerBundle,
 };
 use strum_macros::EnumIter;
 
 use super::super::actors::Actor;
+use crate::controls::ControlBindings;
 
 #[derive(Component, Clone, Copy, PartialEq, Eq, Reflect, Debug)]
 pub struct KeyboardAI;
@@ -23,6 +20,20 @@ pub(crate) enum PlayerAction {
     Shoot4,
 }
 
+impl PlayerAction {
+    pub const SHOOT: [PlayerAction; 4] = [
+        PlayerAction::Shoot1,
+        PlayerAction::Shoot2,
+        PlayerAction::Shoot3,
+        PlayerAction::Shoot4,
+    ];
+
+    /// The fire button that drives the given equipment slot.
+    pub fn shoot(slot: usize) -> Option<PlayerAction> {
+        Self::SHOOT.get(slot).copied()
+    }
+}
+
 impl Actionlike for PlayerAction {
     fn input_control_kind(&self) -> InputControlKind {
         match self {
@@ -32,19 +43,10 @@ impl Actionlike for PlayerAction {
     }
 }
 
-pub(crate) fn create_player_action_input_manager_bundle() -> InputManagerBundle<PlayerAction> {
-    InputManagerBundle::with_map(
-        InputMap::new([
-            (PlayerAction::Shoot3, KeyCode::ShiftLeft),
-            (PlayerAction::Shoot4, KeyCode::KeyF),
-        ])
-        .with(PlayerAction::Shoot1, MouseButton::Left)
-        .with(PlayerAction::Shoot2, MouseButton::Right)
-        .with_dual_axis(
-            PlayerAction::Walk,
-            VirtualDPad::new(KeyCode::KeyW, KeyCode::KeyS, KeyCode::KeyA, KeyCode::KeyD),
-        ),
-    )
+pub(crate) fn create_player_action_input_manager_bundle(
+    bindings: &ControlBindings,
+) -> InputManagerBundle<PlayerAction> {
+    InputManagerBundle::with_map(bindings.input_map())
 }
 
 pub(crate) fn keyboard_input_handler(
@@ -10,7 +10,7 @@ use super::{
 
 use self::{
     keyboard::keyboard_input_handler,
-    tracking::do_tracker_ai,
+    tracking::{aim_at_hostiles, aim_at_player, do_tracker_ai, TrackHostiles, TrackPlayer},
     wander::{ai_wander, PerlinWanderAI},
 };
 
@@ -24,12 +24,16 @@ pub struct AIPlugin;
 impl Plugin for AIPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<PerlinWanderAI>()
+            .register_type::<TrackPlayer>()
+            .register_type::<TrackHostiles>()
             .register_type::<PlayerAction>();
         app.add_plugins(InputManagerPlugin::<PlayerAction>::default());
         app.add_systems(
             Update,
             (
                 do_tracker_ai.run_if(player_exists),
+                aim_at_player.run_if(player_exists),
+                aim_at_hostiles,
                 keyboard_input_handler.run_if(player_exists),
                 ai_wander,
                 normalize_ai.after(do_tracker_ai).after(ai_wander),
@@ -1,15 +1,27 @@
 use bevy::{
     math::Vec3Swizzles,
-    prelude::{Component, Query, Reflect, Transform, With},
+    prelude::{Component, Entity, GlobalTransform, Parent, Query, Reflect, Transform, With},
 };
 
-use crate::twin_stick::{actors::Actor, player::Player};
+use crate::twin_stick::{
+    actors::{Actor, Faction, Tracking},
+    player::Player,
+    weapons::faction_of,
+};
 
 #[derive(Component, Clone, Copy, PartialEq, Reflect, Debug)]
 pub struct TrackerAI {
     pub precision: f32,
 }
 
+/// Points the entity's `Tracking` at the player, for enemy weapons.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Reflect, Debug)]
+pub struct TrackPlayer;
+
+/// Points the entity's `Tracking` at the closest actor of another faction, for deployables.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Reflect, Debug)]
+pub struct TrackHostiles;
+
 pub(crate) fn do_tracker_ai(
     player: Query<&Transform, With<Player>>,
     mut ais: Query<(&mut Actor, &Transform, &TrackerAI)>,
@@ -22,3 +34,33 @@ pub(crate) fn do_tracker_ai(
                 .xy();
     }
 }
+
+pub(crate) fn aim_at_player(
+    player: Query<Entity, With<Player>>,
+    mut trackers: Query<&mut Tracking, With<TrackPlayer>>,
+) {
+    let player = player.single();
+    for mut tracking in trackers.iter_mut().filter(|w| w.0 != Some(player)) {
+        tracking.0 = Some(player);
+    }
+}
+
+pub(crate) fn aim_at_hostiles(
+    mut trackers: Query<(Entity, &mut Tracking, &GlobalTransform), With<TrackHostiles>>,
+    actors: Query<(Entity, &Faction, &GlobalTransform), With<Actor>>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
+) {
+    for (entity, mut tracking, transform) in trackers.iter_mut() {
+        let own = faction_of(entity, &factions, &parents);
+        let closest = actors
+            .iter()
+            .filter(|(_, faction, _)| Some(**faction) != own)
+            .map(|(w, _, target)| (w, target.translation().distance(transform.translation())))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|w| w.0);
+        if tracking.0 != closest {
+            tracking.0 = closest;
+        }
+    }
+}
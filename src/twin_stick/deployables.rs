@@ -0,0 +1,185 @@
+use avian2d::prelude::CollisionLayers;
+use bevy::{
+    app::{App, Update},
+    hierarchy::{Children, DespawnRecursiveExt, HierarchyQueryExt, Parent},
+    prelude::{Added, Commands, Component, Entity, OnInsert, Query, Res, Trigger, With},
+    reflect::Reflect,
+    time::Time,
+};
+use std::time::Duration;
+
+use super::{
+    actors::{Actor, Faction, MISC_ENEMY_FACTION, PLAYER_FACTION},
+    physics::GamePhysicsLayer as GPL,
+    weapons::faction_of,
+};
+use crate::action_system::{actions::spawn::SpawnedBy, triggers::proximity::ProximityTrigger};
+
+/// Something an actor puts down, like a turret. It fights for whoever placed it, and each owner
+/// can only have `limit` of a `kind` out at once, the oldest making way for new ones. A `limit` of
+/// 0 means there is no limit.
+#[derive(Component, Reflect, Clone, Debug, PartialEq, Eq)]
+pub struct Deployable {
+    pub kind: String,
+    pub limit: usize,
+}
+
+impl Deployable {
+    pub fn new(kind: &str, limit: usize) -> Self {
+        Self {
+            kind: kind.to_string(),
+            limit,
+        }
+    }
+}
+
+/// The actor a deployable belongs to, which gets the credit for what it does.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeployedBy {
+    pub owner: Entity,
+    pub placed: Duration,
+}
+
+pub(super) fn deployable_plugin(app: &mut App) {
+    app.register_type::<Deployable>()
+        .register_type::<DeployedBy>();
+    app.add_systems(Update, claim_deployables);
+    app.add_observer(take_owner_side);
+}
+
+/// What a deployable on `faction`'s side collides with, and the factions it should go off at.
+pub fn deployable_side(faction: Faction) -> (CollisionLayers, u16) {
+    match faction {
+        Faction(PLAYER_FACTION) => (
+            CollisionLayers::new(
+                GPL::Player,
+                [GPL::Enemy, GPL::MapSolid, GPL::MapDynamic, GPL::EnemyBullet],
+            ),
+            1 << MISC_ENEMY_FACTION,
+        ),
+        _ => (
+            CollisionLayers::new(
+                GPL::Enemy,
+                [GPL::Player, GPL::MapSolid, GPL::MapDynamic, GPL::Bullet],
+            ),
+            1 << PLAYER_FACTION,
+        ),
+    }
+}
+
+/// Once a deployable knows whose side it's on, it blocks and is hit like that side, and its
+/// proximity triggers look for that side's enemies.
+fn take_owner_side(
+    trigger: Trigger<OnInsert, Faction>,
+    deployables: Query<&Faction, With<Deployable>>,
+    children: Query<&Children>,
+    mut proximity: Query<&mut ProximityTrigger>,
+    mut commands: Commands,
+) {
+    let e = trigger.entity();
+    let Ok(faction) = deployables.get(e) else {
+        return;
+    };
+    let (layers, hostile) = deployable_side(*faction);
+    commands.entity(e).insert(layers);
+    for child in children.iter_descendants(e) {
+        if let Ok(mut trigger) = proximity.get_mut(child) {
+            trigger.triggering_factions = hostile;
+        }
+    }
+}
+
+/// The actor behind `entity`: the one holding it, or whoever placed the deployable holding it.
+pub fn owner_of(
+    entity: Entity,
+    actors: &Query<Option<&DeployedBy>, With<Actor>>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    std::iter::once(entity)
+        .chain(parents.iter_ancestors(entity))
+        .find_map(|w| actors.get(w).ok().map(|deployed| (w, deployed)))
+        .map(|(actor, deployed)| deployed.map(|w| w.owner).unwrap_or(actor))
+}
+
+pub fn claim_deployables(
+    placed: Query<(Entity, &Deployable, &SpawnedBy), Added<Deployable>>,
+    deployed: Query<(Entity, &Deployable, &DeployedBy)>,
+    actors: Query<Option<&DeployedBy>, With<Actor>>,
+    parents: Query<&Parent>,
+    factions: Query<&Faction>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    // Deployables claimed or cleared earlier this frame don't show up in `deployed` yet.
+    let mut claimed: Vec<(Entity, &Deployable, DeployedBy)> = Vec::new();
+    let mut cleared: Vec<Entity> = Vec::new();
+    for (entity, deployable, SpawnedBy(spawner)) in placed.iter() {
+        let Some(owner) = owner_of(*spawner, &actors, &parents) else {
+            continue;
+        };
+        let by = DeployedBy {
+            owner,
+            placed: time.elapsed(),
+        };
+        commands.entity(entity).insert(by);
+        if let Some(faction) = faction_of(owner, &factions, &parents) {
+            commands.entity(entity).insert(faction);
+        }
+
+        if deployable.limit > 0 {
+            let mut existing: Vec<(Entity, Duration)> = deployed
+                .iter()
+                .map(|(w, kind, by)| (w, kind, *by))
+                .chain(claimed.iter().copied())
+                .filter(|(w, kind, by)| {
+                    by.owner == owner && kind.kind == deployable.kind && !cleared.contains(w)
+                })
+                .map(|(w, _, by)| (w, by.placed))
+                .collect();
+            existing.sort_by_key(|w| w.1);
+            let excess = (existing.len() + 1).saturating_sub(deployable.limit);
+            for (oldest, _) in existing.into_iter().take(excess) {
+                commands.entity(oldest).despawn_recursive();
+                cleared.push(oldest);
+            }
+        }
+        claimed.push((entity, deployable, by));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(app: &mut App, owner: Entity, limit: usize) -> Entity {
+        app.world_mut()
+            .spawn((Deployable::new("turret", limit), SpawnedBy(owner)))
+            .id()
+    }
+
+    #[test]
+    fn limit_counts_deployables_placed_the_same_frame() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.add_systems(Update, claim_deployables);
+        let owner = app.world_mut().spawn(Actor::default()).id();
+        let first = place(&mut app, owner, 2);
+        let second = place(&mut app, owner, 2);
+        let third = place(&mut app, owner, 2);
+        app.update();
+        assert!(!app.world().entities().contains(first));
+        assert!(app.world().entities().contains(second));
+        assert!(app.world().entities().contains(third));
+    }
+
+    #[test]
+    fn zero_limit_is_unlimited() {
+        let mut app = App::new();
+        app.init_resource::<Time>();
+        app.add_systems(Update, claim_deployables);
+        let owner = app.world_mut().spawn(Actor::default()).id();
+        let placed: Vec<Entity> = (0..3).map(|_| place(&mut app, owner, 0)).collect();
+        app.update();
+        assert!(placed.iter().all(|w| app.world().entities().contains(*w)));
+    }
+}
@@ -4,6 +4,8 @@ use bevy::{
     reflect::Reflect,
 };
 
+use super::actors::Faction;
+
 #[derive(Event, Clone, Copy, PartialEq, Reflect, Debug)]
 pub struct AttackEvent {
     pub attacker: Entity,
@@ -11,6 +13,9 @@ pub struct AttackEvent {
     pub defender: Entity,
     pub location: Vec2,
     pub direction: Vec2,
+    /// How much of the weapon's damage and knockback lands: 1 for a single hit, less for
+    /// attacks that hit every frame.
+    pub scale: f32,
 }
 
 #[derive(Event, Clone, Copy, PartialEq, Reflect, Debug)]
@@ -26,3 +31,14 @@ pub struct DamageEvent {
     pub source: Entity,
     pub amount: f32,
 }
+
+/// Damages and pushes away everything with health within `radius` of `center`, apart from members
+/// of `spare`.
+#[derive(Event, Clone, Copy, PartialEq, Reflect, Debug)]
+pub struct ExplosionEvent {
+    pub source: Entity,
+    pub center: Vec2,
+    pub radius: f32,
+    pub damage: f32,
+    pub spare: Option<Faction>,
+}
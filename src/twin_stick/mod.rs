@@ -8,6 +8,7 @@ use bevy::{
 
 use bevy_turborand::prelude::RngPlugin;
 use camera::CameraPlugin;
+use deployables::deployable_plugin;
 use player::player_plugin;
 use projectile::projectile_plugin;
 use weapons::weapon_plugin;
@@ -15,6 +16,7 @@ use weapons::weapon_plugin;
 pub mod actors;
 pub mod ai;
 pub mod camera;
+pub mod deployables;
 pub mod events;
 pub mod physics;
 pub mod player;
@@ -33,6 +35,7 @@ impl Plugin for TwinStickPlugin {
         player_plugin(app);
         projectile_plugin(app);
         weapon_plugin(app);
+        deployable_plugin(app);
 
         app.add_plugins(AIPlugin);
 
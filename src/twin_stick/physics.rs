@@ -9,5 +9,7 @@ pub enum GamePhysicsLayer {
     MapSolid,
     MapDynamic,
     Ethereal,
-    Bullet,
+    Bullet, // Fired by the player
+    Trigger,
+    EnemyBullet,
 }
@@ -1,4 +1,6 @@
-use avian2d::prelude::{Collider, CollisionStarted, LinearVelocity, Mass, RigidBody, SweptCcd};
+use avian2d::prelude::{
+    Collider, CollisionLayers, CollisionStarted, LinearVelocity, Mass, RigidBody, SweptCcd,
+};
 use bevy::{
     color::{palettes::css::RED, Color},
     ecs::{schedule::SystemSet, system::ResMut},
@@ -6,19 +8,27 @@ use bevy::{
     math::{Vec2Swizzles, Vec3Swizzles},
     prelude::{
         in_state, App, Commands, Component, DespawnRecursiveExt, Entity, Event, EventReader,
-        EventWriter, IntoSystemConfigs, Query, Reflect, Res, Transform, Update, Vec2, Visibility,
+        EventWriter, IntoSystemConfigs, OnInsert, Query, Reflect, Res, Transform, Trigger, Update,
+        Vec2, Visibility, With,
     },
     sprite::Sprite,
     time::{Time, Timer, TimerMode},
     utils::default,
 };
 use bevy_composable::{app_impl::ComponentTreeable, tree::ComponentTree, wrappers::name};
+use bevy_stats::Stat;
 use std::time::Duration;
 
-use super::events::AttackEvent;
+use super::{
+    actors::{Faction, PLAYER_FACTION},
+    events::{AttackEvent, ExplosionEvent},
+    physics::GamePhysicsLayer as GPL,
+    weapons::faction_of,
+};
 use crate::{
     action_system::actions::spawn::SpawnedBy,
     debug::arrows::{Arrow, Arrows},
+    game::stats::{Damage, Knockback},
     states::TimerState,
 };
 
@@ -52,6 +62,13 @@ pub struct ProjectileClashEvent(pub Entity, pub Entity);
 #[derive(Clone, PartialEq, Eq, Reflect, Debug, Event)]
 pub struct ContactDamage(pub Option<Timer>);
 
+/// Deals `damage` to everything with health within `radius` of where the projectile lands.
+#[derive(Component, Clone, Copy, PartialEq, Reflect, Debug)]
+pub struct Explosive {
+    pub radius: f32,
+    pub damage: f32,
+}
+
 pub fn projectile_plugin(app: &mut App) {
     app.add_systems(
         Update,
@@ -62,6 +79,7 @@ pub fn projectile_plugin(app: &mut App) {
                 (
                     kill_projectiles_post_impact,
                     projectile_hits_trigger_attacks,
+                    explode_on_impact,
                 ),
             )
                 .chain(),
@@ -72,6 +90,66 @@ pub fn projectile_plugin(app: &mut App) {
 
     app.add_event::<ProjectileImpactEvent>()
         .add_event::<ProjectileClashEvent>();
+
+    app.register_type::<Explosive>();
+    app.add_observer(faction_layers)
+        .add_observer(snapshot_weapon_stats);
+}
+
+/// What a projectile nobody's side fired belongs to: it hits actors on both sides and the map, but
+/// not pickups, triggers or other bullets.
+pub fn neutral_projectile_layers() -> CollisionLayers {
+    CollisionLayers::new(
+        [GPL::Bullet, GPL::EnemyBullet],
+        [GPL::Player, GPL::Enemy, GPL::MapSolid, GPL::MapDynamic],
+    )
+}
+
+/// Projectiles take their collision layers from whichever side they're on.
+fn faction_layers(
+    trigger: Trigger<OnInsert, Faction>,
+    projectiles: Query<&Faction, With<Projectile>>,
+    mut commands: Commands,
+) {
+    if let Ok(faction) = projectiles.get(trigger.entity()) {
+        commands
+            .entity(trigger.entity())
+            .insert(projectile_layers(*faction));
+    }
+}
+
+/// Copies the damage and knockback of whatever fired a projectile onto it, so changes to the
+/// weapon after the shot don't affect it.
+fn snapshot_weapon_stats(
+    trigger: Trigger<OnInsert, SpawnedBy>,
+    projectiles: Query<&SpawnedBy, With<Projectile>>,
+    damages: Query<&Stat<Damage>>,
+    knockbacks: Query<&Stat<Knockback>>,
+    mut commands: Commands,
+) {
+    let Ok(SpawnedBy(spawner)) = projectiles.get(trigger.entity()) else {
+        return;
+    };
+    let mut projectile = commands.entity(trigger.entity());
+    if let Ok(damage) = damages.get(*spawner) {
+        projectile.insert(Stat::<Damage>::new(damage.current_value()));
+    }
+    if let Ok(knockback) = knockbacks.get(*spawner) {
+        projectile.insert(Stat::<Knockback>::new(knockback.current_value()));
+    }
+}
+
+/// What a projectile fired by `faction` belongs to and can hit.
+pub fn projectile_layers(faction: Faction) -> CollisionLayers {
+    match faction {
+        Faction(PLAYER_FACTION) => {
+            CollisionLayers::new(GPL::Bullet, [GPL::Enemy, GPL::MapSolid, GPL::MapDynamic])
+        }
+        _ => CollisionLayers::new(
+            GPL::EnemyBullet,
+            [GPL::Player, GPL::MapSolid, GPL::MapDynamic],
+        ),
+    }
 }
 
 pub fn projectile(lifespan: f32, projectile: Projectile) -> ComponentTree {
@@ -83,6 +161,8 @@ pub fn projectile(lifespan: f32, projectile: Projectile) -> ComponentTree {
         Collider::circle(3.),
         SweptCcd::default(),
         Mass(0.1),
+        // Replaced by `projectile_layers` once the projectile gets a side.
+        neutral_projectile_layers(),
         Sprite {
             color: Color::Srgba(RED),
             custom_size: Some(Vec2::new(6., 6.)),
@@ -162,6 +242,7 @@ fn projectile_hits_trigger_attacks(
     mut attack_events: EventWriter<AttackEvent>,
     transforms: Query<&Transform>,
     bullets: Query<(&SpawnedBy, Option<&LinearVelocity>)>,
+    snapshots: Query<(), With<Stat<Damage>>>,
     gun_owners: Query<&Parent>,
     mut arrows: ResMut<Arrows>,
 ) {
@@ -176,9 +257,15 @@ fn projectile_hits_trigger_attacks(
         );
         let location = projectile_pos.translation.xy();
         if let Ok((SpawnedBy(spawner), velocity)) = bullets.get(*projectile) {
-            let (weapon, attacker): (&Entity, &Entity) = match gun_owners.get(*spawner) {
-                Ok(owner) => (spawner, &*owner),
-                Err(_) => (spawner, spawner),
+            let attacker = match gun_owners.get(*spawner) {
+                Ok(owner) => owner.get(),
+                Err(_) => *spawner,
+            };
+            // A projectile carrying its own stats stands in for the weapon that fired it.
+            let weapon = if snapshots.contains(*projectile) {
+                *projectile
+            } else {
+                *spawner
             };
             let direction = match velocity {
                 Some(vel) => vel.yx(),
@@ -190,16 +277,39 @@ fn projectile_hits_trigger_attacks(
                 duration: Timer::default(),
             });
             attack_events.send(AttackEvent {
-                attacker: *attacker,
-                weapon: *weapon,
+                attacker,
+                weapon,
                 defender: *impacted,
                 location,
                 direction,
+                scale: 1.,
             });
         }
     }
 }
 
+fn explode_on_impact(
+    mut projectile_events: EventReader<ProjectileImpactEvent>,
+    mut explosions: EventWriter<ExplosionEvent>,
+    explosives: Query<(&Explosive, &Transform, Option<&SpawnedBy>)>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
+) {
+    for ProjectileImpactEvent { projectile, .. } in projectile_events.read() {
+        let Ok((explosive, transform, spawner)) = explosives.get(*projectile) else {
+            continue;
+        };
+        let source = spawner.map(|w| w.0).unwrap_or(*projectile);
+        explosions.send(ExplosionEvent {
+            source,
+            center: transform.translation.xy(),
+            radius: explosive.radius,
+            damage: explosive.damage,
+            spare: faction_of(source, &factions, &parents),
+        });
+    }
+}
+
 fn kill_projectiles_post_impact(
     mut events: EventReader<ProjectileImpactEvent>,
     mut commands: Commands,
@@ -3,17 +3,21 @@ use bevy::{
     app::{App, Update},
     ecs::{
         component::Component,
+        entity::Entity,
         event::{EventReader, EventWriter},
         query::{Or, With},
         schedule::IntoSystemConfigs,
-        system::Query,
+        system::{Commands, Query, Res, Resource},
     },
+    hierarchy::{HierarchyQueryExt, Parent},
+    math::{Vec2, Vec3Swizzles},
     reflect::Reflect,
     state::condition::in_state,
+    transform::components::Transform,
 };
 use bevy_stats::{
     statmod::{ModType, StatValueChange},
-    ResourceChangeEvent, Stat,
+    Resource, ResourceChangeEvent, Stat,
 };
 
 use crate::{
@@ -22,51 +26,108 @@ use crate::{
 };
 
 use super::{
-    actors::Actor,
-    events::{AttackEvent, DamageEvent, KnockbackEvent},
+    actors::{Actor, Faction, Invulnerable, Stunned},
+    deployables::DeployedBy,
+    events::{AttackEvent, DamageEvent, ExplosionEvent, KnockbackEvent},
+    projectile::Projectile,
 };
 
 #[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct Weapon;
 
+/// Stuns the actors a weapon hits for this many seconds.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct StunOnHit(pub f32);
+
+/// Whether attacks and explosions can hurt the attacker's own faction. Collision layers still decide
+/// what a bullet can touch, this decides whether touching hurts.
+#[derive(Resource, Reflect, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FriendlyFire(pub bool);
+
 pub fn weapon_plugin(app: &mut App) {
     app.add_event::<KnockbackEvent>()
         .add_event::<DamageEvent>()
-        .add_event::<AttackEvent>();
+        .add_event::<AttackEvent>()
+        .add_event::<ExplosionEvent>();
 
     app.register_type::<KnockbackEvent>()
         .register_type::<DamageEvent>()
         .register_type::<AttackEvent>()
-        .register_type::<Weapon>();
+        .register_type::<ExplosionEvent>()
+        .register_type::<Weapon>()
+        .register_type::<StunOnHit>()
+        .register_type::<FriendlyFire>();
+    app.init_resource::<FriendlyFire>();
 
     app.add_systems(
         Update,
         (
             (knockback_from_attacks, impart_knockback).chain(),
+            stun_from_attacks,
             (damage_from_attacks, impart_damage).chain(),
+            detonate_explosions
+                .before(impart_damage)
+                .before(impart_knockback),
         )
             .run_if(in_state(TimerState::Playing)),
     );
 }
 
+/// The faction of `entity`, or of its closest ancestor with one.
+pub fn faction_of(
+    entity: Entity,
+    factions: &Query<&Faction>,
+    parents: &Query<&Parent>,
+) -> Option<Faction> {
+    std::iter::once(entity)
+        .chain(parents.iter_ancestors(entity))
+        .find_map(|w| factions.get(w).ok().copied())
+}
+
+/// Whether an attack lands, which it doesn't on the attacker's own side unless friendly fire is on.
+fn attack_lands(
+    attack: &AttackEvent,
+    friendly_fire: &FriendlyFire,
+    factions: &Query<&Faction>,
+    parents: &Query<&Parent>,
+) -> bool {
+    if friendly_fire.0 {
+        return true;
+    }
+    match (
+        faction_of(attack.attacker, factions, parents),
+        faction_of(attack.defender, factions, parents),
+    ) {
+        (Some(attacker), Some(defender)) => attacker != defender,
+        _ => true,
+    }
+}
+
 pub(crate) fn knockback_from_attacks(
     mut projectile_events: EventReader<AttackEvent>,
     mut knockback_events: EventWriter<KnockbackEvent>,
-    weapons: Query<&Stat<Knockback>, Or<(With<Weapon>, With<Actor>)>>,
+    weapons: Query<&Stat<Knockback>, Or<(With<Weapon>, With<Actor>, With<Projectile>)>>,
+    friendly_fire: Res<FriendlyFire>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
 ) {
-    for AttackEvent {
-        attacker,
-        weapon,
-        defender,
-        location: _,
-        direction,
-    } in projectile_events.read()
+    for attack in projectile_events
+        .read()
+        .filter(|w| attack_lands(w, &friendly_fire, &factions, &parents))
     {
+        let AttackEvent {
+            attacker: _,
+            weapon,
+            defender,
+            location: _,
+            direction,
+            scale,
+        } = attack;
         if let Ok(knockback) = weapons.get(*weapon) {
             knockback_events.send(KnockbackEvent {
                 entity: *defender,
                 direction: *direction,
-                force: knockback.current_value(),
+                force: knockback.current_value() * scale,
             });
         }
     }
@@ -75,22 +136,89 @@ pub(crate) fn knockback_from_attacks(
 pub(crate) fn damage_from_attacks(
     mut damage_events: EventWriter<DamageEvent>,
     mut projectile_events: EventReader<AttackEvent>,
-    damagers: Query<&Stat<Damage>, Or<(With<Weapon>, With<Actor>)>>,
+    damagers: Query<&Stat<Damage>, Or<(With<Weapon>, With<Actor>, With<Projectile>)>>,
+    deployed: Query<&DeployedBy>,
+    friendly_fire: Res<FriendlyFire>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
 ) {
-    for AttackEvent {
-        attacker,
-        weapon,
-        defender,
-        location: _,
-        direction: _,
-    } in projectile_events.read()
+    for attack in projectile_events
+        .read()
+        .filter(|w| attack_lands(w, &friendly_fire, &factions, &parents))
     {
+        let AttackEvent {
+            attacker,
+            weapon,
+            defender,
+            location: _,
+            direction: _,
+            scale,
+        } = attack;
         if let Ok(damage) = damagers.get(*weapon) {
+            // Whoever placed a deployable gets the credit for its damage.
             damage_events.send(DamageEvent {
                 target: *defender,
-                source: *attacker,
-                amount: damage.current_value(),
+                source: deployed
+                    .get(*attacker)
+                    .map(|w| w.owner)
+                    .unwrap_or(*attacker),
+                amount: damage.current_value() * scale,
+            });
+        }
+    }
+}
+
+pub(crate) fn detonate_explosions(
+    mut explosions: EventReader<ExplosionEvent>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut knockback_events: EventWriter<KnockbackEvent>,
+    targets: Query<(Entity, &Transform, Option<&Faction>), With<Resource<Health>>>,
+    friendly_fire: Res<FriendlyFire>,
+) {
+    for explosion in explosions.read() {
+        for (target, transform, faction) in targets.iter() {
+            if !friendly_fire.0 && explosion.spare.is_some() && faction == explosion.spare.as_ref()
+            {
+                continue;
+            }
+            let offset = transform.translation.xy() - explosion.center;
+            if offset.length() > explosion.radius {
+                continue;
+            }
+            damage_events.send(DamageEvent {
+                target,
+                source: explosion.source,
+                amount: explosion.damage,
             });
+            if offset != Vec2::ZERO {
+                knockback_events.send(KnockbackEvent {
+                    entity: target,
+                    direction: offset,
+                    force: explosion.damage,
+                });
+            }
+        }
+    }
+}
+
+pub fn stun_from_attacks(
+    mut attack_events: EventReader<AttackEvent>,
+    stuns: Query<&StunOnHit>,
+    actors: Query<(), With<Actor>>,
+    friendly_fire: Res<FriendlyFire>,
+    factions: Query<&Faction>,
+    parents: Query<&Parent>,
+    mut commands: Commands,
+) {
+    for attack in attack_events
+        .read()
+        .filter(|w| attack_lands(w, &friendly_fire, &factions, &parents))
+    {
+        let Ok(StunOnHit(secs)) = stuns.get(attack.weapon) else {
+            continue;
+        };
+        if actors.contains(attack.defender) {
+            commands.entity(attack.defender).insert(Stunned::new(*secs));
         }
     }
 }
@@ -115,12 +243,15 @@ fn impart_knockback(
 fn impart_damage(
     mut damage_events: EventReader<DamageEvent>,
     mut resource_changes: EventWriter<ResourceChangeEvent<Health>>,
+    invulnerable: Query<(), With<Invulnerable>>,
 ) {
     for DamageEvent {
         target,
         source: _,
         amount,
-    } in damage_events.read()
+    } in damage_events
+        .read()
+        .filter(|w| !invulnerable.contains(w.target))
     {
         resource_changes.send(ResourceChangeEvent {
             change: StatValueChange::new(amount * -1., ModType::Offset),
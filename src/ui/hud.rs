@@ -1,6 +1,47 @@
+use bevy::prelude::{Children, Name, Query, Res, With};
 use bevy_egui::{egui, EguiContexts};
+use bevy_stats::Resource as StatResource;
 
-pub(crate) fn hud_gui(mut root: EguiContexts) {
+use crate::{
+    game::{
+        boss::Boss,
+        director::EncounterDirector,
+        equipment::Equipment,
+        modifiers::WeaponModifier,
+        stats::{Health, ResourceMax},
+    },
+    twin_stick::player::Player,
+};
+
+pub(crate) fn hud_gui(
+    mut root: EguiContexts,
+    players: Query<&Equipment, With<Player>>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    modifiers: Query<(), With<WeaponModifier>>,
+    director: Option<Res<EncounterDirector>>,
+    bosses: Query<(&Boss, &StatResource<Health>, &ResourceMax<Health>)>,
+) {
+    for (boss, health, max) in bosses.iter() {
+        egui::Window::new(boss.name.as_str())
+            .resizable(false)
+            .collapsible(false)
+            .scroll([false, false])
+            .enabled(true)
+            .anchor(egui::Align2::CENTER_TOP, egui::Vec2::new(0., 20.))
+            .show(root.ctx_mut(), |ui| {
+                ui.add(
+                    egui::ProgressBar::new(health.current_value() / max.value.max(1.))
+                        .desired_width(400.)
+                        .fill(egui::Color32::from_rgb(180, 40, 40))
+                        .text(format!(
+                            "Phase {}/{}",
+                            boss.current.unwrap_or(0) + 1,
+                            boss.phases.len()
+                        )),
+                );
+            });
+    }
     egui::Window::new("HUD1")
         .resizable(false)
         .collapsible(false)
@@ -10,10 +51,16 @@ pub(crate) fn hud_gui(mut root: EguiContexts) {
         .show(root.ctx_mut(), |ui| {
             ui.horizontal(|ui| {
                 ui.label("Items:");
-                let temp = vec!["Fireball", "Gun", "Turret", "Amulet", "", ""];
-                for item in temp.into_iter() {
+                let Ok(equipment) = players.get_single() else {
+                    return;
+                };
+                for (i, slot) in equipment.slots.iter().enumerate() {
+                    let item = slot
+                        .and_then(|w| names.get(w).ok())
+                        .map(|w| w.as_str())
+                        .unwrap_or("");
                     egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
-                        ui.label(item);
+                        ui.label(format!("{}: {}", i + 1, item));
                     });
                 }
             })
@@ -28,6 +75,18 @@ pub(crate) fn hud_gui(mut root: EguiContexts) {
             ui.horizontal(|ui| {
                 ui.label("Health:");
                 ui.colored_label(egui::Color32::from_rgb(100, 255, 100), "000000XX");
+                if let Some(director) = &director {
+                    if director.cleared {
+                        ui.label("Arena cleared, find the exit");
+                    } else if !director.waves.is_empty() {
+                        ui.label(format!(
+                            "Wave {}/{} ({} left)",
+                            director.next,
+                            director.waves.len(),
+                            director.living
+                        ));
+                    }
+                }
             })
         });
     egui::Window::new("HUD3")
@@ -38,10 +97,20 @@ pub(crate) fn hud_gui(mut root: EguiContexts) {
         .anchor(egui::Align2::RIGHT_CENTER, egui::Vec2::default())
         .show(root.ctx_mut(), |ui| {
             ui.vertical(|ui| {
-                let temp = vec!["Faster", "AoE", "Dubler", "Eraser"];
-                for item in temp.into_iter() {
+                let Ok(equipment) = players.get_single() else {
+                    return;
+                };
+                let attached = equipment
+                    .slots
+                    .iter()
+                    .flatten()
+                    .filter_map(|w| children.get(*w).ok())
+                    .flat_map(|w| w.iter())
+                    .filter(|w| modifiers.contains(**w))
+                    .filter_map(|w| names.get(*w).ok());
+                for item in attached {
                     egui::Frame::dark_canvas(ui.style()).show(ui, |ui| {
-                        ui.label(item);
+                        ui.label(item.as_str());
                     });
                 }
             })
@@ -0,0 +1,163 @@
+use bevy::prelude::{
+    Children, Commands, DespawnRecursiveExt, Entity, EventWriter, Name, NextState, Query, ResMut,
+    With,
+};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    game::{
+        equipment::{Equipment, EquipmentEvent},
+        inventory::{use_consumable, Inventory, ItemKind},
+        modifiers::{attach_modifier, WeaponModifier},
+    },
+    states::UIState,
+    twin_stick::player::Player,
+};
+
+enum InventoryCommand {
+    Equip(usize, usize),
+    Attach(usize, Entity),
+    Detach(Entity),
+    Use(usize),
+    Discard(usize),
+    Unequip(usize),
+}
+
+pub(crate) fn inventory_gui(
+    mut root: EguiContexts,
+    mut players: Query<(Entity, &mut Inventory, &Equipment), With<Player>>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    modifiers: Query<Entity, With<WeaponModifier>>,
+    mut equipment_events: EventWriter<EquipmentEvent>,
+    mut menu_state: ResMut<NextState<UIState>>,
+    mut commands: Commands,
+) {
+    let Ok((player, mut inventory, equipment)) = players.get_single_mut() else {
+        return;
+    };
+    let mut command = None;
+
+    egui::Window::new("Inventory")
+        .resizable(false)
+        .collapsible(false)
+        .scroll([false, true])
+        .enabled(true)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::default())
+        .show(root.ctx_mut(), |ui| {
+            ui.heading("Equipped");
+            for (slot, weapon) in equipment.slots.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let item = weapon
+                        .and_then(|w| names.get(w).ok())
+                        .map(|w| w.as_str())
+                        .unwrap_or("-");
+                    ui.label(format!("{}: {}", slot + 1, item));
+                    if weapon.is_some() && ui.button("Unequip").clicked() {
+                        command = Some(InventoryCommand::Unequip(slot));
+                    }
+                });
+                let attached = weapon
+                    .and_then(|w| children.get(w).ok())
+                    .into_iter()
+                    .flat_map(|w| w.iter())
+                    .filter_map(|w| modifiers.get(*w).ok());
+                for modifier in attached {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "    + {}",
+                            names.get(modifier).map(|w| w.as_str()).unwrap_or("")
+                        ));
+                        if ui.button("Detach").clicked() {
+                            command = Some(InventoryCommand::Detach(modifier));
+                        }
+                    });
+                }
+            }
+
+            for (kind, heading) in [
+                (ItemKind::Weapon, "Weapons"),
+                (ItemKind::Modifier, "Modifiers"),
+                (ItemKind::Consumable, "Consumables"),
+            ] {
+                ui.separator();
+                ui.heading(heading);
+                for (i, item) in inventory
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, w)| w.kind == kind)
+                {
+                    ui.horizontal(|ui| {
+                        ui.label(&item.name);
+                        match kind {
+                            ItemKind::Weapon => {
+                                for slot in 0..equipment.slots.len() {
+                                    if ui.button(format!("Equip {}", slot + 1)).clicked() {
+                                        command = Some(InventoryCommand::Equip(i, slot));
+                                    }
+                                }
+                            }
+                            ItemKind::Consumable => {
+                                if ui.button("Use").clicked() {
+                                    command = Some(InventoryCommand::Use(i));
+                                }
+                            }
+                            ItemKind::Modifier => {
+                                for (slot, weapon) in equipment.slots.iter().enumerate() {
+                                    if let Some(weapon) = weapon {
+                                        if ui.button(format!("Attach {}", slot + 1)).clicked() {
+                                            command = Some(InventoryCommand::Attach(i, *weapon));
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if ui.button("Discard").clicked() {
+                            command = Some(InventoryCommand::Discard(i));
+                        }
+                    });
+                }
+            }
+
+            ui.separator();
+            if ui.button("Close").clicked() {
+                menu_state.set(UIState::None);
+            }
+        });
+
+    match command {
+        Some(InventoryCommand::Equip(index, slot)) => {
+            if let Some(item) = inventory.take(index) {
+                equipment_events.send(EquipmentEvent::Equip {
+                    owner: player,
+                    slot,
+                    weapon: item.tree,
+                });
+            }
+        }
+        Some(InventoryCommand::Attach(index, weapon)) => {
+            if let Some(item) = inventory.take(index) {
+                attach_modifier(&mut commands, weapon, &item);
+            }
+        }
+        Some(InventoryCommand::Detach(modifier)) => {
+            commands.entity(modifier).despawn_recursive();
+        }
+        Some(InventoryCommand::Use(index)) => {
+            if let Some(item) = inventory.take(index) {
+                use_consumable(&mut commands, player, &item);
+            }
+        }
+        Some(InventoryCommand::Discard(index)) => {
+            inventory.take(index);
+        }
+        Some(InventoryCommand::Unequip(slot)) => {
+            equipment_events.send(EquipmentEvent::Drop {
+                owner: player,
+                slot,
+            });
+        }
+        None => (),
+    }
+}
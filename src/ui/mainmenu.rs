@@ -1,9 +1,20 @@
 use bevy::prelude::{NextState, ResMut};
 use bevy_egui::{egui, EguiContexts};
+use strum::IntoEnumIterator;
 
-use crate::states::AppState;
+use crate::{
+    game::difficulty::{Difficulty, DifficultySettings},
+    states::{AppState, UIState},
+    twin_stick::weapons::FriendlyFire,
+};
 
-pub fn main_menu_gui(mut root: EguiContexts, mut state: ResMut<NextState<AppState>>) {
+pub fn main_menu_gui(
+    mut root: EguiContexts,
+    mut state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<UIState>>,
+    mut difficulty: ResMut<DifficultySettings>,
+    mut friendly_fire: ResMut<FriendlyFire>,
+) {
     egui::CentralPanel::default().show(root.ctx_mut(), |ui| {
         ui.allocate_space(egui::Vec2::new(1.0, 100.0));
 
@@ -13,8 +24,23 @@ pub fn main_menu_gui(mut root: EguiContexts, mut state: ResMut<NextState<AppStat
                 if ui.button("Play Game").clicked() {
                     state.set(AppState::Game);
                 }
+                ui.horizontal(|ui| {
+                    ui.label("Difficulty:");
+                    let mut selected = difficulty.selected;
+                    for option in Difficulty::iter() {
+                        ui.selectable_value(&mut selected, option, option.label());
+                    }
+                    if selected != difficulty.selected {
+                        difficulty.selected = selected;
+                    }
+                });
+                let mut enabled = friendly_fire.0;
+                ui.checkbox(&mut enabled, "Friendly fire");
+                if enabled != friendly_fire.0 {
+                    friendly_fire.0 = enabled;
+                }
                 if ui.button("Options").clicked() {
-                    todo!();
+                    menu_state.set(UIState::Options);
                 }
                 if ui.button("Quit").clicked() {
                     state.set(AppState::Exit);
@@ -1,16 +1,25 @@
 use bevy::{
     app::{Plugin, Update},
-    prelude::{in_state, IntoSystemConfigs},
+    prelude::{in_state, resource_exists, IntoSystemConfigs, OnEnter},
 };
 use bevy_egui::EguiPlugin;
 use hud::hud_gui;
+use inventory::inventory_gui;
 use mainmenu::main_menu_gui;
+use options::{options_gui, remember_options_origin, OptionsOpenedFrom};
+use overmap::overmap_gui;
 use pausemenu::pause_gui;
 
-use crate::states::{AppState, GameState, UIState};
+use crate::{
+    game::run::Run,
+    states::{AppState, GameState, UIState},
+};
 
 pub mod hud;
+pub mod inventory;
 pub mod mainmenu;
+pub mod options;
+pub mod overmap;
 pub mod pausemenu;
 
 pub struct UiPlugin;
@@ -26,6 +35,22 @@ impl Plugin for UiPlugin {
                 .run_if(in_state(UIState::Pause))
                 .run_if(in_state(AppState::Game)),
         );
+        app.init_resource::<OptionsOpenedFrom>();
+        app.add_systems(OnEnter(UIState::Options), remember_options_origin);
+        app.add_systems(Update, options_gui.run_if(in_state(UIState::Options)));
+        app.add_systems(
+            Update,
+            inventory_gui
+                .run_if(in_state(UIState::Inventory))
+                .run_if(in_state(AppState::Game)),
+        );
+        app.add_systems(
+            Update,
+            overmap_gui
+                .run_if(in_state(GameState::OverMap))
+                .run_if(in_state(AppState::Game))
+                .run_if(resource_exists::<Run>),
+        );
         app.add_systems(
             Update,
             hud_gui
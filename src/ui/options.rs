@@ -0,0 +1,92 @@
+use bevy::{
+    log::warn,
+    prelude::{EventReader, NextState, Res, ResMut, Resource, StateTransitionEvent},
+};
+use bevy_egui::{egui, EguiContexts};
+use strum::IntoEnumIterator;
+
+use crate::{
+    controls::{BindingSlot, ControlBindings, Rebinding},
+    states::UIState,
+};
+
+/// The menu Options was opened from, which "Back" returns to.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub(crate) struct OptionsOpenedFrom(pub UIState);
+
+pub(crate) fn remember_options_origin(
+    mut transitions: EventReader<StateTransitionEvent<UIState>>,
+    mut opened_from: ResMut<OptionsOpenedFrom>,
+) {
+    if let Some(exited) = transitions.read().filter_map(|w| w.exited).last() {
+        if exited != UIState::Options {
+            opened_from.0 = exited;
+        }
+    }
+}
+
+pub(crate) fn options_gui(
+    mut root: EguiContexts,
+    mut bindings: ResMut<ControlBindings>,
+    mut rebinding: ResMut<Rebinding>,
+    opened_from: Res<OptionsOpenedFrom>,
+    mut menu_state: ResMut<NextState<UIState>>,
+) {
+    let conflicts = bindings.conflicts();
+    egui::Window::new("Options")
+        .resizable(false)
+        .collapsible(false)
+        .scroll([false, false])
+        .enabled(true)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::default())
+        .show(root.ctx_mut(), |ui| {
+            egui::Grid::new("bindings").striped(true).show(ui, |ui| {
+                for slot in BindingSlot::iter() {
+                    ui.label(slot.label());
+                    let text = if rebinding.0 == Some(slot) {
+                        // Mouse presses are ignored for walk slots, see `BindingSlot::accepts`.
+                        egui::RichText::new(if slot.takes_mouse() {
+                            "Press a key or button..."
+                        } else {
+                            "Press a key..."
+                        })
+                    } else if conflicts.contains(&slot) {
+                        egui::RichText::new(bindings.get(slot).to_string())
+                            .color(egui::Color32::from_rgb(255, 100, 100))
+                    } else {
+                        egui::RichText::new(bindings.get(slot).to_string())
+                    };
+                    if ui.button(text).clicked() {
+                        rebinding.0 = Some(slot);
+                    }
+                    ui.end_row();
+                }
+            });
+
+            if !conflicts.is_empty() {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 100, 100),
+                    "Some inputs are bound to more than one action.",
+                );
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Defaults").clicked() {
+                    *bindings = ControlBindings::default();
+                    rebinding.0 = None;
+                }
+                if ui
+                    .add_enabled(conflicts.is_empty(), egui::Button::new("Save"))
+                    .clicked()
+                {
+                    if let Err(e) = bindings.save() {
+                        warn!("Could not save controls: {}", e);
+                    }
+                }
+                if ui.button("Back").clicked() {
+                    rebinding.0 = None;
+                    menu_state.set(opened_from.0);
+                }
+            });
+        });
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::{NextState, ResMut};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{game::run::Run, states::GameState};
+
+pub(crate) fn overmap_gui(
+    mut root: EguiContexts,
+    mut run: ResMut<Run>,
+    mut state: ResMut<NextState<GameState>>,
+) {
+    let available = run.available();
+    let mut picked = None;
+
+    egui::CentralPanel::default().show(root.ctx_mut(), |ui| {
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
+            ui.label(format!("Seed {}", run.seed));
+            for layer in run.layers.iter() {
+                ui.add_space(30.);
+                ui.horizontal(|ui| {
+                    for id in layer.iter() {
+                        let node = &run.nodes[*id];
+                        let label = node.kind.label();
+                        let text = if node.cleared {
+                            egui::RichText::new(label).strikethrough()
+                        } else if run.current == Some(*id) {
+                            egui::RichText::new(label).strong()
+                        } else {
+                            egui::RichText::new(label)
+                        };
+                        if ui
+                            .add_enabled(available.contains(id), egui::Button::new(text))
+                            .clicked()
+                        {
+                            picked = Some(*id);
+                        }
+                    }
+                });
+            }
+        });
+    });
+
+    if let Some(id) = picked {
+        run.current = Some(id);
+        state.set(GameState::InLevel);
+    }
+}